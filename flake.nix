@@ -293,6 +293,7 @@
             pkgs.rustup
             pkgs.trunk
             pkgs.wasm-pack
+            pkgs.nodejs
             pkgs.tokei
           ];
         };
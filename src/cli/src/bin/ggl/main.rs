@@ -1,5 +1,11 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
+use graph_generation_language::format;
+use graph_generation_language::generators::GENERATORS;
+use graph_generation_language::layout::{self, LayoutOptions};
+use graph_generation_language::parser::parse_ggl;
+use graph_generation_language::types::Graph;
 use graph_generation_language::GGLEngine;
+use serde::Serialize;
 use std::fs;
 use std::io::{self, Read};
 use std::path::PathBuf;
@@ -12,6 +18,9 @@ use std::path::PathBuf;
 )]
 /// Command-line interface for the Graph Generation Language (GGL)
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Input GGL file to process
     #[arg(short, long)]
     input: Option<PathBuf>,
@@ -24,51 +33,225 @@ struct Args {
     #[arg(short, long)]
     pretty: bool,
 
+    /// Emit compact JSON without any whitespace
+    #[arg(long, conflicts_with = "pretty")]
+    minify: bool,
+
     /// Verbose output
     #[arg(short, long)]
     verbose: bool,
+
+    /// Compare the generated graph against a previously generated graph JSON file
+    /// and output the differences instead of the graph
+    #[arg(long, value_name = "FILE")]
+    diff: Option<PathBuf>,
+
+    /// Seed for randomized generators, making the output reproducible
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Abort if the graph would contain more than this many nodes
+    #[arg(long)]
+    max_nodes: Option<usize>,
+
+    /// Abort if the graph would contain more than this many edges
+    #[arg(long)]
+    max_edges: Option<usize>,
+
+    /// Add `x`/`y` coordinates to every node using the named layout (circle, grid, concentric, random, force)
+    #[arg(long)]
+    layout: Option<String>,
+
+    /// Print the parsed syntax tree instead of running the program
+    #[arg(long)]
+    dump_ast: bool,
+
+    /// List the matches each applied rule found just before it was applied (on stderr)
+    #[arg(long)]
+    explain_rules: bool,
+
+    /// Log every builtin call and the size of its result (on stderr)
+    #[arg(long)]
+    trace: bool,
+
+    /// Number of simulation steps for the force layout
+    #[arg(long, default_value_t = 50)]
+    layout_iterations: usize,
 }
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let args = Args::parse();
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// List the available generators and their parameters
+    Generators,
+    /// Print a GGL program in canonical formatting
+    Fmt {
+        /// GGL file to format (defaults to stdin)
+        file: Option<PathBuf>,
+    },
+}
 
-    if args.verbose {
-        eprintln!(
-            "Graph Generation Language CLI v{}",
-            env!("CARGO_PKG_VERSION")
-        );
+/// Prints every generator with its parameters, e.g. `prefix (string, default "n"): ...`.
+fn print_generators() {
+    for (i, generator) in GENERATORS.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!("{}: {}", generator.name, generator.description);
+        for param in generator.params {
+            let usage = match (param.required, param.default) {
+                (true, _) => "required".to_string(),
+                (false, Some(default)) => format!("default {default}"),
+                (false, None) => "optional".to_string(),
+            };
+            println!(
+                "  {} ({}, {usage}): {}",
+                param.name, param.kind, param.description
+            );
+        }
     }
+}
 
-    // Read input
-    let ggl_code = match args.input {
+/// Reads GGL source from `path`, or from stdin if no path is given.
+fn read_source(path: Option<&PathBuf>, verbose: bool) -> Result<String, String> {
+    match path {
         Some(path) => {
-            if args.verbose {
+            if verbose {
                 eprintln!("Reading GGL code from: {}", path.display());
             }
-            fs::read_to_string(&path)
-                .map_err(|e| format!("Failed to read input file '{}': {}", path.display(), e))?
+            fs::read_to_string(path)
+                .map_err(|e| format!("Failed to read input file '{}': {}", path.display(), e))
         }
         None => {
-            if args.verbose {
+            if verbose {
                 eprintln!("Reading GGL code from stdin...");
             }
             let mut buffer = String::new();
             io::stdin()
                 .read_to_string(&mut buffer)
                 .map_err(|e| format!("Failed to read from stdin: {e}"))?;
-            buffer
+            Ok(buffer)
         }
-    };
+    }
+}
+
+/// Serializes CLI output, compactly if `--minify` was given.
+fn format_json<T: Serialize>(value: &T, minify: bool) -> serde_json::Result<String> {
+    if minify {
+        serde_json::to_string(value)
+    } else {
+        serde_json::to_string_pretty(value)
+    }
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    match args.command {
+        Some(Command::Generators) => {
+            print_generators();
+            return Ok(());
+        }
+        Some(Command::Fmt { file }) => {
+            let source = read_source(file.as_ref(), false)?;
+            print!("{}", format::format_ggl(&source)?);
+            return Ok(());
+        }
+        None => {}
+    }
+
+    if args.verbose {
+        eprintln!(
+            "Graph Generation Language CLI v{}",
+            env!("CARGO_PKG_VERSION")
+        );
+    }
+
+    // Read input
+    let ggl_code = read_source(args.input.as_ref(), args.verbose)?;
+
+    if args.dump_ast {
+        let ast = parse_ggl(&ggl_code).map_err(|e| format!("Parse error: {e}"))?;
+        println!("{ast:#?}");
+        return Ok(());
+    }
 
     if args.verbose {
         eprintln!("Processing GGL code ({} characters)", ggl_code.len());
     }
 
     // Process with GGL engine
-    let mut engine = GGLEngine::new();
-    let result = engine
-        .generate_from_ggl(&ggl_code)
-        .map_err(|e| format!("GGL processing error: {e}"))?;
+    let mut engine = GGLEngine::new()
+        .with_profiling(args.verbose)
+        .with_trace(args.trace)
+        .with_rule_explanations(args.explain_rules)
+        .with_minified_output(args.minify);
+    if let Some(seed) = args.seed {
+        engine = engine.with_seed(seed);
+    }
+    if let Some(max_nodes) = args.max_nodes {
+        engine = engine.with_max_nodes(max_nodes);
+    }
+    if let Some(max_edges) = args.max_edges {
+        engine = engine.with_max_edges(max_edges);
+    }
+    let result = engine.generate_from_ggl(&ggl_code);
+    // Print the trace before any error so it shows the calls leading up to it
+    for line in engine.trace() {
+        eprintln!("trace: {line}");
+    }
+    for (name, matches) in engine.explain_rules() {
+        eprintln!("Rule '{name}': {} match(es) before apply", matches.len());
+        for m in matches {
+            let mut bindings: Vec<_> = m.node_mapping.iter().collect();
+            bindings.sort();
+            let bindings: Vec<String> = bindings
+                .into_iter()
+                .map(|(pattern, node)| format!("{pattern}={node}"))
+                .collect();
+            eprintln!("  {}", bindings.join(", "));
+        }
+    }
+    let result = result.map_err(|e| format!("GGL processing error: {e}"))?;
+
+    if args.verbose {
+        let timings = engine.timings();
+        eprintln!("Parse time: {:?}", timings.parse);
+        eprintln!("Generation time: {:?}", timings.generation);
+        eprintln!("Rule application time: {:?}", timings.rules);
+        eprintln!("Serialization time: {:?}", timings.serialization);
+    }
+
+    // Add node coordinates, if requested
+    let mut graph = engine.get_graph().clone();
+    let result = match &args.layout {
+        Some(name) => {
+            let options = LayoutOptions {
+                seed: args.seed,
+                iterations: args.layout_iterations,
+                ..LayoutOptions::default()
+            };
+            layout::apply_layout(&mut graph, name, &options)
+                .map_err(|e| format!("Layout error: {e}"))?;
+            format_json(&graph, args.minify).map_err(|e| format!("Serialization error: {e}"))?
+        }
+        None => result,
+    };
+
+    // Replace the graph with its differences from the baseline, if requested
+    let result = match &args.diff {
+        Some(path) => {
+            if args.verbose {
+                eprintln!("Comparing against: {}", path.display());
+            }
+            let baseline_json = fs::read_to_string(path)
+                .map_err(|e| format!("Failed to read diff file '{}': {}", path.display(), e))?;
+            let baseline: Graph = serde_json::from_str(&baseline_json)
+                .map_err(|e| format!("Failed to parse diff file '{}': {}", path.display(), e))?;
+            let diff = baseline.diff(&graph);
+            format_json(&diff, args.minify).map_err(|e| format!("Failed to format diff: {e}"))?
+        }
+        None => result,
+    };
 
     // Format output
     let output = if args.pretty {
@@ -0,0 +1,242 @@
+use serde_json::Value;
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+/// Runs the `ggl` binary with the given arguments, feeding `input` on stdin.
+fn run_ggl(args: &[&str], input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_ggl"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to start ggl");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout_json(output: &Output) -> Value {
+    assert!(
+        output.status.success(),
+        "ggl failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[test]
+fn test_seed_makes_generation_reproducible() {
+    let program = r#"
+        graph scale_free {
+            generate barabasi_albert {
+                nodes: 30;
+                edges_per_node: 2;
+            }
+        }
+    "#;
+
+    let first = stdout_json(&run_ggl(&["--seed", "42"], program));
+    let second = stdout_json(&run_ggl(&["--seed", "42"], program));
+    assert_eq!(first, second);
+    assert_eq!(first["nodes"].as_object().unwrap().len(), 30);
+}
+
+#[test]
+fn test_max_nodes_aborts_runaway_generation() {
+    let program = "graph { generate complete { nodes: 100000; } }";
+
+    let output = run_ggl(&["--max-nodes", "500"], program);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("exceeding the limit of 500"), "stderr: {stderr}");
+}
+
+#[test]
+fn test_verbose_reports_phase_timings() {
+    let program = r#"
+        graph timed {
+            generate star { nodes: 5; }
+            rule tag {
+                lhs { node a; }
+                rhs { node a :tagged; }
+            }
+            apply tag 1 times;
+        }
+    "#;
+
+    let output = run_ggl(&["--verbose"], program);
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    for phase in [
+        "Parse time:",
+        "Generation time:",
+        "Rule application time:",
+        "Serialization time:",
+    ] {
+        assert!(stderr.contains(phase), "missing '{phase}' in: {stderr}");
+    }
+}
+
+#[test]
+fn test_layout_adds_coordinates() {
+    let program = r#"
+        graph ring {
+            generate cycle {
+                nodes: 6;
+            }
+        }
+    "#;
+
+    let graph = stdout_json(&run_ggl(&["--layout", "circle"], program));
+    for node in graph["nodes"].as_object().unwrap().values() {
+        assert!(node["metadata"]["x"].is_f64());
+        assert!(node["metadata"]["y"].is_f64());
+    }
+
+    let output = run_ggl(&["--layout", "spiral"], program);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_force_layout_is_reproducible() {
+    let program = r#"
+        graph mesh {
+            generate grid {
+                rows: 3;
+                cols: 3;
+            }
+        }
+    "#;
+    let args = ["--layout", "force", "--layout-iterations", "20"];
+
+    let first = stdout_json(&run_ggl(&args, program));
+    let second = stdout_json(&run_ggl(&args, program));
+    assert_eq!(first, second);
+    assert!(first["nodes"]["n0_0"]["metadata"]["x"].is_f64());
+}
+
+#[test]
+fn test_minify_output_has_no_whitespace() {
+    let program = r#"
+        graph small {
+            node a [label="first node"];
+            node b;
+            edge a -> b [weight=2];
+        }
+    "#;
+
+    for args in [&["--minify"][..], &["--minify", "--layout", "circle"][..]] {
+        let output = run_ggl(args, program);
+        let graph = stdout_json(&output);
+        assert_eq!(graph["nodes"]["a"]["metadata"]["label"], "first node");
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        let json = stdout.trim_end_matches('\n');
+        assert!(!json.contains('\n'), "minified output spans lines: {json}");
+        assert!(!json.contains(": "));
+    }
+
+    let output = run_ggl(&["--minify", "--pretty"], program);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_explain_rules_lists_matches() {
+    let program = r#"
+        graph todo {
+            node a [kind="todo"];
+            node b [kind="todo"];
+            node c [kind="todo"];
+            rule finish {
+                lhs { node A [kind="todo"]; }
+                rhs { node A [kind="done"]; }
+            }
+            apply finish 1 times;
+            apply finish 1 times;
+        }
+    "#;
+
+    let output = run_ggl(&["--explain-rules"], program);
+    let graph = stdout_json(&output);
+    assert_eq!(graph["nodes"]["c"]["metadata"]["kind"], "done");
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let counts: Vec<&str> = stderr.lines().filter(|l| l.starts_with("Rule ")).collect();
+    // Matches are counted before each apply, not in the final graph
+    assert_eq!(
+        counts,
+        [
+            "Rule 'finish': 3 match(es) before apply",
+            "Rule 'finish': 0 match(es) before apply"
+        ]
+    );
+    assert!(stderr.contains("  A=a\n  A=b\n  A=c\n"));
+}
+
+#[test]
+fn test_trace_logs_builtin_calls() {
+    let output = run_ggl(
+        &["--trace"],
+        "graph g { node a [n=sum(shuffle([1, 2, 3]))]; }",
+    );
+    let graph = stdout_json(&output);
+    assert_eq!(graph["nodes"]["a"]["metadata"]["n"], 6);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let trace: Vec<&str> = stderr.lines().filter(|l| l.starts_with("trace: ")).collect();
+    assert_eq!(
+        trace,
+        [
+            "trace: shuffle([3 items]) -> [3 items]",
+            "trace: sum([3 items]) -> 6"
+        ]
+    );
+}
+
+#[test]
+fn test_generators_subcommand_lists_generators() {
+    let output = run_ggl(&["generators"], "");
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("complete: Complete graph (clique)"));
+    assert!(stdout.contains("grid: 2D grid graph"));
+    assert!(stdout.contains("  rows (int, required): Number of rows"));
+    assert!(stdout.contains(r#"  prefix (string, default "n"): Prefix for node IDs"#));
+}
+
+#[test]
+fn test_fmt_subcommand_formats_stdin() {
+    let output = run_ggl(&["fmt"], "graph g{node a[x=1+2*3];edge a->a;}");
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "graph g {\n    node a [x=1 + 2 * 3];\n    edge a -> a;\n}\n"
+    );
+
+    let output = run_ggl(&["fmt"], "graph g { node }");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Parse error"));
+
+    let output = run_ggl(&["fmt"], "graph g { node a; // keep me\n}");
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Cannot format source with '//'"));
+}
+
+#[test]
+fn test_dump_ast_prints_syntax_tree() {
+    let output = run_ggl(
+        &["--dump-ast"],
+        r#"graph g { let config = {size: 2}; node a [x=config]; generate nope { } }"#,
+    );
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("GraphAST {"));
+    assert!(stdout.contains("Let("));
+    assert!(stdout.contains("Object("));
+    assert!(stdout.contains("Node("));
+    // The program is not run, so the unknown generator goes unnoticed
+    assert!(stdout.contains("\"nope\""));
+}
@@ -0,0 +1,73 @@
+//! Helpers for getting the generated JSON out of the browser.
+
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = console)]
+    fn log(s: &str);
+}
+
+/// Returns a download file name derived from the graph name in the GGL program,
+/// e.g. `graph social_network { ... }` becomes `social_network.json`.
+pub fn json_filename(ggl_code: &str) -> String {
+    let name = ggl_code
+        .split_whitespace()
+        .skip_while(|word| *word != "graph")
+        .nth(1)
+        .map(|word| word.trim_end_matches('{'))
+        .filter(|word| {
+            !word.is_empty() && word.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        })
+        .unwrap_or("graph");
+    format!("{name}.json")
+}
+
+/// Copies `text` to the system clipboard.
+pub fn copy_to_clipboard(text: &str) {
+    let copy_code = format!(
+        "navigator.clipboard.writeText({}).catch(function(e) {{ console.error('Copy failed:', e); }});",
+        serde_json::Value::from(text)
+    );
+    if let Err(e) = js_sys::eval(&copy_code) {
+        log(&format!("Error copying to clipboard: {e:?}"));
+    }
+}
+
+/// Offers `text` as a JSON file download named `filename`.
+pub fn download_json(text: &str, filename: &str) {
+    let download_code = format!(
+        r#"
+        (function() {{
+            const blob = new Blob([{}], {{ type: 'application/json' }});
+            const link = document.createElement('a');
+            link.href = URL.createObjectURL(blob);
+            link.download = {};
+            link.click();
+            URL.revokeObjectURL(link.href);
+        }})();
+        "#,
+        serde_json::Value::from(text),
+        serde_json::Value::from(filename)
+    );
+    if let Err(e) = js_sys::eval(&download_code) {
+        log(&format!("Error downloading JSON: {e:?}"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_filename_from_graph_name() {
+        assert_eq!(json_filename("graph social_network {\n}"), "social_network.json");
+        assert_eq!(json_filename("// comment\ngraph mesh{ node a; }"), "mesh.json");
+    }
+
+    #[test]
+    fn test_json_filename_fallback() {
+        assert_eq!(json_filename("graph { node a; }"), "graph.json");
+        assert_eq!(json_filename(""), "graph.json");
+    }
+}
@@ -1,9 +1,29 @@
+mod export;
 mod monaco_editor;
+mod permalink;
+mod persistence;
 
-use monaco_editor::MonacoEditor;
+use monaco_editor::{EditorMarker, MonacoEditor};
+use persistence::BrowserStore;
 use yew::prelude::*;
 use graph_generation_language::GGLEngine;
 
+/// Program shown on first visit and after resetting the editor.
+const EXAMPLE_PROGRAM: &str = r#"graph social_network {
+    // Define nodes with types and attributes
+    node alice :person [name="Alice", age=30];
+    node bob :person [name="Bob", age=25];
+
+    // Create relationships
+    edge friendship: alice -- bob [strength=0.8];
+
+    // Generate additional structure
+    generate complete {
+        nodes: 3;
+        prefix: "user";
+    }
+}"#;
+
 pub struct App {
     ggl_input: String,
     json_output: Option<Result<String, String>>,
@@ -12,6 +32,10 @@ pub struct App {
 pub enum Msg {
     EditorChanged(String),
     Generate,
+    CopyJson,
+    DownloadJson,
+    ResetExample,
+    CopyLink,
 }
 
 impl Component for App {
@@ -19,21 +43,24 @@ impl Component for App {
     type Properties = ();
 
     fn create(_ctx: &Context<Self>) -> Self {
+        let linked = js_sys::eval("window.location.hash")
+            .ok()
+            .and_then(|hash| hash.as_string())
+            .and_then(|hash| permalink::program_from_fragment(&hash));
+        let ggl_input = match linked {
+            Some(program) => {
+                // Keep the linked program, but drop the fragment so that later reloads
+                // restore the saved edits instead of the link
+                persistence::save_program(&BrowserStore, &program);
+                let _ = js_sys::eval(
+                    "history.replaceState(null, '', window.location.pathname + window.location.search)",
+                );
+                program
+            }
+            None => persistence::restore_program(&BrowserStore, EXAMPLE_PROGRAM),
+        };
         Self {
-            ggl_input: r#"graph social_network {
-    // Define nodes with types and attributes
-    node alice :person [name="Alice", age=30];
-    node bob :person [name="Bob", age=25];
-
-    // Create relationships
-    edge friendship: alice -- bob [strength=0.8];
-
-    // Generate additional structure
-    generate complete {
-        nodes: 3;
-        prefix: "user";
-    }
-}"#.to_string(),
+            ggl_input,
             json_output: None,
         }
     }
@@ -41,6 +68,7 @@ impl Component for App {
     fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             Msg::EditorChanged(value) => {
+                persistence::save_program(&BrowserStore, &value);
                 self.ggl_input = value;
                 false
             }
@@ -70,11 +98,52 @@ impl Component for App {
                 }
                 true
             }
+            Msg::CopyJson => {
+                if let Some(Ok(json)) = &self.json_output {
+                    export::copy_to_clipboard(json);
+                }
+                false
+            }
+            Msg::DownloadJson => {
+                if let Some(Ok(json)) = &self.json_output {
+                    export::download_json(json, &export::json_filename(&self.ggl_input));
+                }
+                false
+            }
+            Msg::CopyLink => {
+                if let Some(current_value) = MonacoEditor::get_value() {
+                    self.ggl_input = current_value;
+                }
+                // Build the link without touching the current URL
+                let link_code = format!(
+                    "window.location.origin + window.location.pathname + {}",
+                    serde_json::Value::from(permalink::program_fragment(&self.ggl_input))
+                );
+                match js_sys::eval(&link_code).ok().and_then(|url| url.as_string()) {
+                    Some(url) => export::copy_to_clipboard(&url),
+                    None => web_sys::console::error_1(&"Failed to create permalink".into()),
+                }
+                false
+            }
+            Msg::ResetExample => {
+                persistence::clear_program(&BrowserStore);
+                self.ggl_input = EXAMPLE_PROGRAM.to_string();
+                true
+            }
         }
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
+        let markers: Vec<EditorMarker> = match &self.json_output {
+            Some(Err(error)) => EditorMarker::from_error(error).into_iter().collect(),
+            _ => Vec::new(),
+        };
         let on_generate = ctx.link().callback(|_| Msg::Generate);
+        let on_copy = ctx.link().callback(|_| Msg::CopyJson);
+        let on_download = ctx.link().callback(|_| Msg::DownloadJson);
+        let on_reset = ctx.link().callback(|_| Msg::ResetExample);
+        let on_copy_link = ctx.link().callback(|_| Msg::CopyLink);
+        let has_json = matches!(self.json_output, Some(Ok(_)));
         let on_editor_change = ctx.link().callback(|value: String| Msg::EditorChanged(value));
 
         html! {
@@ -85,8 +154,9 @@ impl Component for App {
                         <MonacoEditor
                             initial_value={self.ggl_input.clone()}
                             on_change={on_editor_change}
-                            language="null"
+                            language="ggl"
                             theme="vs-dark"
+                            markers={markers}
                         />
                     </div>
                     <div style="height: 100%; width: 12px;"></div>
@@ -98,6 +168,18 @@ impl Component for App {
                 <button class="generate-btn" onclick={on_generate}>
                     {"🔄 Generate Graph"}
                 </button>
+                <button class="generate-btn export-btn" onclick={on_copy} disabled={!has_json}>
+                    {"📋 Copy JSON"}
+                </button>
+                <button class="generate-btn export-btn" onclick={on_download} disabled={!has_json}>
+                    {"💾 Download JSON"}
+                </button>
+                <button class="generate-btn export-btn" onclick={on_copy_link}>
+                    {"🔗 Copy Link"}
+                </button>
+                <button class="generate-btn export-btn" onclick={on_reset}>
+                    {"↩ Reset to Example"}
+                </button>
             </div>
         }
     }
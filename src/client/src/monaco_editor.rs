@@ -8,6 +8,84 @@ extern "C" {
     fn log(s: &str);
 }
 
+/// Registers the `ggl` language with Monaco: a Monarch tokenizer that highlights
+/// GGL keywords, strings (including `{var}` interpolations), numbers and comments.
+const GGL_LANGUAGE_SETUP: &str = r#"
+    if (!monaco.languages.getLanguages().some(function(lang) { return lang.id === 'ggl'; })) {
+        monaco.languages.register({ id: 'ggl' });
+        monaco.languages.setMonarchTokensProvider('ggl', {
+            keywords: ['graph', 'node', 'edge', 'let', 'for', 'while', 'in', 'generate', 'rule', 'lhs', 'rhs', 'apply', 'times', 'match'],
+            constants: ['true', 'false', 'null'],
+            tokenizer: {
+                root: [
+                    [/\$[a-zA-Z_]\w*/, 'variable'],
+                    [/[a-zA-Z_]\w*/, { cases: { '@keywords': 'keyword', '@constants': 'constant', '@default': 'identifier' } }],
+                    [/\/\/.*$/, 'comment'],
+                    [/#.*$/, 'comment.doc'],
+                    [/\/\*/, 'comment', '@comment'],
+                    [/"/, 'string', '@string'],
+                    [/-?\d+\.\d+/, 'number.float'],
+                    [/-?\d+/, 'number'],
+                    [/->|--/, 'operator'],
+                    [/[{}\[\]()]/, '@brackets'],
+                ],
+                comment: [
+                    [/[^*]+/, 'comment'],
+                    [/\*\//, 'comment', '@pop'],
+                    [/\*/, 'comment'],
+                ],
+                string: [
+                    [/\{[a-zA-Z_]\w*\}/, 'variable'],
+                    [/[^"{]+/, 'string'],
+                    [/\{/, 'string'],
+                    [/"/, 'string', '@pop'],
+                ],
+            },
+        });
+    }
+"#;
+
+/// An error marker (red squiggle) shown at a position in the editor.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EditorMarker {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl EditorMarker {
+    /// Extracts the position from an engine error message.
+    ///
+    /// Parse errors contain a ` --> line:column` location followed by a
+    /// `= expected ...` explanation; errors without a location yield `None`.
+    pub fn from_error(error: &str) -> Option<Self> {
+        let location = error.split("--> ").nth(1)?;
+        let location = location.split_whitespace().next()?;
+        let (line, column) = location.split_once(':')?;
+        let message = error
+            .lines()
+            .find_map(|l| l.trim_start().strip_prefix("= "))
+            .unwrap_or(error)
+            .to_string();
+        Some(EditorMarker {
+            line: line.parse().ok()?,
+            column: column.parse().ok()?,
+            message,
+        })
+    }
+
+    fn to_monaco(&self) -> serde_json::Value {
+        serde_json::json!({
+            "startLineNumber": self.line,
+            "startColumn": self.column,
+            "endLineNumber": self.line,
+            "endColumn": self.column + 1,
+            "message": self.message,
+            "severity": 8, // monaco.MarkerSeverity.Error
+        })
+    }
+}
+
 #[derive(Properties, PartialEq)]
 pub struct MonacoEditorProps {
     pub initial_value: String,
@@ -18,6 +96,8 @@ pub struct MonacoEditorProps {
     pub theme: Option<String>,
     #[prop_or_default]
     pub readonly: bool,
+    #[prop_or_default]
+    pub markers: Vec<EditorMarker>,
 }
 
 pub struct MonacoEditor {
@@ -102,6 +182,9 @@ impl Component for MonacoEditor {
                 log(&format!("Error updating Monaco value: {e:?}"));
             }
         }
+        if self.monaco_ready && ctx.props().markers != old_props.markers {
+            self.set_markers(&ctx.props().markers);
+        }
         false
     }
 }
@@ -122,6 +205,7 @@ impl MonacoEditor {
             r#"
             require.config({{ paths: {{ vs: 'https://cdnjs.cloudflare.com/ajax/libs/monaco-editor/0.44.0/min/vs' }} }});
             require(['vs/editor/editor.main'], function(monaco) {{
+                {}
                 const container = document.getElementById('{}');
                 if (container) {{
                     const editor = monaco.editor.create(container, {{
@@ -153,6 +237,7 @@ impl MonacoEditor {
                 }}
             }});
             "#,
+            if language == "ggl" { GGL_LANGUAGE_SETUP } else { "" },
             editor_id,
             initial_value.replace('`', r#"\`"#).replace("${", r#"\${"#),
             language,
@@ -183,6 +268,21 @@ impl MonacoEditor {
         link.send_message(MonacoMsg::MonacoReady);
     }
 
+    /// Replaces the error markers shown in this editor.
+    fn set_markers(&self, markers: &[EditorMarker]) {
+        let editor_var = format!("monacoEditor_{}", self.editor_id.replace("-", "_"));
+        let markers: Vec<serde_json::Value> = markers.iter().map(EditorMarker::to_monaco).collect();
+        let set_markers_code = format!(
+            "if (window['{}'] && window.monaco) {{ monaco.editor.setModelMarkers(window['{}'].getModel(), 'ggl', {}); }}",
+            editor_var,
+            editor_var,
+            serde_json::Value::Array(markers)
+        );
+        if let Err(e) = js_sys::eval(&set_markers_code) {
+            log(&format!("Error setting Monaco markers: {e:?}"));
+        }
+    }
+
     /// Get the current value from the Monaco editor
     pub fn get_value() -> Option<String> {
         match js_sys::eval("window.monacoEditor ? window.monacoEditor.getValue() : null") {
@@ -203,3 +303,22 @@ impl MonacoEditor {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_marker_from_parse_error() {
+        let error = "Parse error:  --> 4:1\n  |\n4 | }\n  | ^---\n  |\n  = expected attributes";
+        let marker = EditorMarker::from_error(error).unwrap();
+        assert_eq!(marker.line, 4);
+        assert_eq!(marker.column, 1);
+        assert_eq!(marker.message, "expected attributes");
+    }
+
+    #[test]
+    fn test_marker_from_error_without_position() {
+        assert_eq!(EditorMarker::from_error("Unknown rule: grow"), None);
+    }
+}
@@ -0,0 +1,81 @@
+//! Shareable links that carry the GGL program in the URL fragment.
+//!
+//! The program is stored as `#code=<base64url>` so it never reaches a server.
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Fragment prefix identifying an encoded program.
+const FRAGMENT_PREFIX: &str = "#code=";
+
+/// Encodes a program as unpadded base64url of its UTF-8 bytes.
+pub fn encode_program(program: &str) -> String {
+    let mut encoded = String::with_capacity(program.len().div_ceil(3) * 4);
+    for chunk in program.as_bytes().chunks(3) {
+        let bits = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |acc, (i, byte)| acc | (*byte as u32) << (16 - 8 * i));
+        for i in 0..=chunk.len() {
+            encoded.push(ALPHABET[(bits >> (18 - 6 * i) & 0x3f) as usize] as char);
+        }
+    }
+    encoded
+}
+
+/// Decodes a program produced by [`encode_program`], or `None` if it is malformed.
+pub fn decode_program(encoded: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(encoded.len() / 4 * 3);
+    for chunk in encoded.as_bytes().chunks(4) {
+        if chunk.len() == 1 {
+            return None;
+        }
+        let mut bits = 0u32;
+        for (i, c) in chunk.iter().enumerate() {
+            let value = ALPHABET.iter().position(|a| a == c)? as u32;
+            bits |= value << (18 - 6 * i);
+        }
+        for i in 0..chunk.len() - 1 {
+            bytes.push((bits >> (16 - 8 * i)) as u8);
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+/// Returns the URL fragment for sharing `program`.
+pub fn program_fragment(program: &str) -> String {
+    format!("{FRAGMENT_PREFIX}{}", encode_program(program))
+}
+
+/// Extracts the program from a URL fragment created by [`program_fragment`].
+pub fn program_from_fragment(fragment: &str) -> Option<String> {
+    decode_program(fragment.strip_prefix(FRAGMENT_PREFIX)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        for program in ["", "g", "gr", "graph g { node a; }", "graph ü { node \"ñ→✓\"; } // 🌐"] {
+            let fragment = program_fragment(program);
+            assert!(fragment[FRAGMENT_PREFIX.len()..]
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+            assert_eq!(program_from_fragment(&fragment).as_deref(), Some(program));
+        }
+    }
+
+    #[test]
+    fn test_known_encoding() {
+        assert_eq!(encode_program("graph"), "Z3JhcGg");
+        assert_eq!(decode_program("Z3JhcGg").as_deref(), Some("graph"));
+    }
+
+    #[test]
+    fn test_malformed_fragment() {
+        assert_eq!(program_from_fragment("#other=abc"), None);
+        assert_eq!(program_from_fragment("#code=a"), None);
+        assert_eq!(program_from_fragment("#code=a*bc"), None);
+    }
+}
@@ -0,0 +1,84 @@
+//! Keeps the editor content across page reloads.
+
+use gloo::storage::{LocalStorage, Storage};
+
+/// Storage key under which the editor content is saved.
+const PROGRAM_KEY: &str = "ggl_input";
+
+/// A string key-value store, implemented by the browser's `localStorage`.
+pub trait ProgramStore {
+    fn get(&self, key: &str) -> Option<String>;
+    fn set(&self, key: &str, value: &str);
+    fn remove(&self, key: &str);
+}
+
+/// [`ProgramStore`] backed by `window.localStorage`.
+pub struct BrowserStore;
+
+impl ProgramStore for BrowserStore {
+    fn get(&self, key: &str) -> Option<String> {
+        LocalStorage::get(key).ok()
+    }
+
+    fn set(&self, key: &str, value: &str) {
+        // Storage may be full or disabled; losing the saved copy is not fatal.
+        let _ = LocalStorage::set(key, value);
+    }
+
+    fn remove(&self, key: &str) {
+        LocalStorage::delete(key);
+    }
+}
+
+/// Returns the saved program, or `default` if nothing has been saved.
+pub fn restore_program(store: &impl ProgramStore, default: &str) -> String {
+    store
+        .get(PROGRAM_KEY)
+        .unwrap_or_else(|| default.to_string())
+}
+
+/// Saves the program so it is restored on the next page load.
+pub fn save_program(store: &impl ProgramStore, program: &str) {
+    store.set(PROGRAM_KEY, program);
+}
+
+/// Forgets the saved program.
+pub fn clear_program(store: &impl ProgramStore) {
+    store.remove(PROGRAM_KEY);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    #[derive(Default)]
+    struct MemoryStore(RefCell<HashMap<String, String>>);
+
+    impl ProgramStore for MemoryStore {
+        fn get(&self, key: &str) -> Option<String> {
+            self.0.borrow().get(key).cloned()
+        }
+
+        fn set(&self, key: &str, value: &str) {
+            self.0.borrow_mut().insert(key.to_string(), value.to_string());
+        }
+
+        fn remove(&self, key: &str) {
+            self.0.borrow_mut().remove(key);
+        }
+    }
+
+    #[test]
+    fn test_save_and_restore_program() {
+        let store = MemoryStore::default();
+        assert_eq!(restore_program(&store, "graph example {}"), "graph example {}");
+
+        save_program(&store, "graph mine { node a; }");
+        assert_eq!(restore_program(&store, "graph example {}"), "graph mine { node a; }");
+
+        clear_program(&store);
+        assert_eq!(restore_program(&store, "graph example {}"), "graph example {}");
+    }
+}
@@ -0,0 +1,40 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use graph_generation_language::GGLEngine;
+
+const GRID_PROGRAM: &str = r#"
+    graph bench {
+        generate grid {
+            rows: 100;
+            cols: 100;
+        }
+    }
+"#;
+
+const RULES_PROGRAM: &str = r#"
+    graph bench {
+        generate barabasi_albert {
+            nodes: 500;
+            edges_per_node: 3;
+            seed: 7;
+        }
+        rule tag {
+            lhs { node N; }
+            rhs { node N [visited=true]; }
+        }
+        apply tag 100 times;
+    }
+"#;
+
+fn bench_generation(c: &mut Criterion) {
+    // One engine driven repeatedly; every run resets its state first
+    let mut engine = GGLEngine::new().with_seed(42);
+    c.bench_function("grid 100x100", |b| {
+        b.iter(|| engine.generate_from_ggl(black_box(GRID_PROGRAM)).unwrap())
+    });
+    c.bench_function("barabasi_albert 500 with rules", |b| {
+        b.iter(|| engine.generate_from_ggl(black_box(RULES_PROGRAM)).unwrap())
+    });
+}
+
+criterion_group!(benches, bench_generation);
+criterion_main!(benches);
@@ -0,0 +1,331 @@
+//! # Source Formatter
+//!
+//! Re-emits a parsed GGL program as canonical source: four-space indentation, one
+//! statement per line, single spaces around operators and after commas, and only
+//! the parentheses operator precedence requires. `#` doc comments are kept.
+//! `//` and `/* */` comments are not part of the AST, so sources containing them
+//! are refused rather than reformatted without them.
+
+use crate::parser::{
+    find_comment, parse_ggl, BinaryOp, EdgeDeclaration, Expression, ForIterable, GraphAST, NodeDeclaration,
+    Pattern, Statement, StringPart,
+};
+use std::fmt::Write;
+
+const INDENT: &str = "    ";
+/// Precedence of the comparison operators, which do not chain.
+const COMPARISON: u8 = 1;
+
+/// Parses `source` and returns it formatted. Formatting the result again yields
+/// the same text. Fails if `source` contains `//` or `/* */` comments, which
+/// would otherwise be lost.
+pub fn format_ggl(source: &str) -> Result<String, String> {
+    let ast = parse_ggl(source).map_err(|e| format!("Parse error: {e}"))?;
+    if let Some(offset) = find_comment(source) {
+        let (line, column) = pest::Position::new(source, offset).unwrap().line_col();
+        return Err(format!(
+            "Cannot format source with '//' or '/* */' comments, which would be lost (line {line}, column {column}); use '#' doc comments or remove them"
+        ));
+    }
+    Ok(format_ast(&ast))
+}
+
+/// Renders a program as canonical GGL source.
+pub fn format_ast(ast: &GraphAST) -> String {
+    let mut out = String::new();
+    if ast.name.is_empty() {
+        out.push_str("graph {\n");
+    } else {
+        let _ = writeln!(out, "graph {} {{", ast.name);
+    }
+    write_statements(&mut out, &ast.statements, 1);
+    out.push_str("}\n");
+    out
+}
+
+fn write_line(out: &mut String, depth: usize, line: &str) {
+    for _ in 0..depth {
+        out.push_str(INDENT);
+    }
+    out.push_str(line);
+    out.push('\n');
+}
+
+fn write_block(out: &mut String, depth: usize, header: &str, body: &[Statement]) {
+    write_line(out, depth, &format!("{header} {{"));
+    write_statements(out, body, depth + 1);
+    write_line(out, depth, "}");
+}
+
+fn write_statements(out: &mut String, statements: &[Statement], depth: usize) {
+    for statement in statements {
+        match statement {
+            Statement::Let(stmt) => write_line(
+                out,
+                depth,
+                &format!("let {} = {};", stmt.name, format_expression(&stmt.value)),
+            ),
+            Statement::For(stmt) => {
+                let iterable = match &stmt.iterable {
+                    ForIterable::Range { start, end } => {
+                        format!("{}..{}", format_expression(start), format_expression(end))
+                    }
+                    ForIterable::Items(items) => format_expression(items),
+                };
+                write_block(
+                    out,
+                    depth,
+                    &format!("for {} in {iterable}", stmt.variable),
+                    &stmt.body,
+                );
+            }
+            Statement::While(stmt) => write_block(
+                out,
+                depth,
+                &format!("while {}", format_expression(&stmt.condition)),
+                &stmt.body,
+            ),
+            Statement::Node(node) => write_node(out, depth, node),
+            Statement::Edge(edge) => write_edge(out, depth, edge),
+            Statement::Generate(stmt) => {
+                write_line(out, depth, &format!("generate {} {{", stmt.name));
+                for (key, value) in &stmt.params {
+                    write_line(out, depth + 1, &format!("{key}: {};", format_expression(value)));
+                }
+                write_line(out, depth, "}");
+            }
+            Statement::RuleDef(rule) => {
+                write_line(out, depth, &format!("rule {} {{", rule.name));
+                write_pattern(out, depth + 1, "lhs", &rule.lhs);
+                write_pattern(out, depth + 1, "rhs", &rule.rhs);
+                write_line(out, depth, "}");
+            }
+            Statement::Apply(stmt) => write_line(
+                out,
+                depth,
+                &format!(
+                    "apply {} {} times;",
+                    stmt.rule_name,
+                    format_expression(&stmt.iterations)
+                ),
+            ),
+        }
+    }
+}
+
+fn write_pattern(out: &mut String, depth: usize, side: &str, pattern: &Pattern) {
+    write_line(out, depth, &format!("{side} {{"));
+    for node in &pattern.nodes {
+        write_node(out, depth + 1, node);
+    }
+    for edge in &pattern.edges {
+        write_edge(out, depth + 1, edge);
+    }
+    write_line(out, depth, "}");
+}
+
+fn write_doc(out: &mut String, depth: usize, doc: &Option<String>) {
+    for line in doc.iter().flat_map(|doc| doc.lines()) {
+        if line.is_empty() {
+            write_line(out, depth, "#");
+        } else {
+            write_line(out, depth, &format!("# {line}"));
+        }
+    }
+}
+
+fn write_node(out: &mut String, depth: usize, node: &NodeDeclaration) {
+    write_doc(out, depth, &node.doc);
+    let mut line = format!("node {}", format_expression(&node.id));
+    if let Some(node_type) = &node.node_type {
+        let _ = write!(line, " :{}", format_expression(node_type));
+    }
+    line.push_str(&format_attributes(&node.attributes));
+    line.push(';');
+    write_line(out, depth, &line);
+}
+
+fn write_edge(out: &mut String, depth: usize, edge: &EdgeDeclaration) {
+    write_doc(out, depth, &edge.doc);
+    let mut line = String::from("edge ");
+    if let Some(id) = &edge.id {
+        let _ = write!(line, "{}: ", format_expression(id));
+    }
+    let operator = if edge.directed { "->" } else { "--" };
+    let _ = write!(
+        line,
+        "{} {operator} {}",
+        format_expression(&edge.source),
+        format_expression(&edge.target)
+    );
+    line.push_str(&format_attributes(&edge.attributes));
+    line.push(';');
+    write_line(out, depth, &line);
+}
+
+fn format_attributes(attributes: &[(String, Expression)]) -> String {
+    if attributes.is_empty() {
+        return String::new();
+    }
+    let pairs: Vec<String> = attributes
+        .iter()
+        .map(|(key, value)| format!("{key}={}", format_expression(value)))
+        .collect();
+    format!(" [{}]", pairs.join(", "))
+}
+
+/// Binding strength of an operator: `??`, comparisons, then `+`/`-`, then `*`/`/`/`%`.
+fn precedence(op: BinaryOp) -> u8 {
+    match op {
+        BinaryOp::Mul | BinaryOp::Div | BinaryOp::Mod => 3,
+        BinaryOp::Add | BinaryOp::Sub => 2,
+        BinaryOp::Coalesce => 0,
+        _ => COMPARISON,
+    }
+}
+
+fn is_identifier(text: &str) -> bool {
+    let mut chars = text.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn format_list(items: &[Expression]) -> String {
+    items
+        .iter()
+        .map(format_expression)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Renders an expression as GGL source.
+pub fn format_expression(expr: &Expression) -> String {
+    match expr {
+        Expression::StringLiteral(s) => format!("\"{s}\""),
+        Expression::FormattedString(parts) => {
+            let mut text = String::from("\"");
+            for part in parts {
+                match part {
+                    StringPart::Literal(s) => text.push_str(s),
+                    StringPart::Variable(name) => {
+                        let _ = write!(text, "{{{name}}}");
+                    }
+                    StringPart::Expression(e) => {
+                        let _ = write!(text, "{{{}}}", format_expression(e));
+                    }
+                }
+            }
+            text.push('"');
+            text
+        }
+        Expression::Integer(i) => i.to_string(),
+        Expression::Float(f) => {
+            // Keep a fractional part so the value reads back as a float
+            let text = f.to_string();
+            if text.contains('.') {
+                text
+            } else {
+                format!("{text}.0")
+            }
+        }
+        Expression::Boolean(b) => b.to_string(),
+        Expression::Null => "null".to_string(),
+        Expression::Identifier(name) => name.clone(),
+        Expression::Variable(name) => format!("${name}"),
+        Expression::Wildcard => "*".to_string(),
+        Expression::Array(items) => format!("[{}]", format_list(items)),
+        Expression::Object(pairs) => {
+            let pairs: Vec<String> = pairs
+                .iter()
+                .map(|(key, value)| {
+                    let value = format_expression(value);
+                    if is_identifier(key) {
+                        format!("{key}: {value}")
+                    } else {
+                        format!("\"{key}\": {value}")
+                    }
+                })
+                .collect();
+            format!("{{{}}}", pairs.join(", "))
+        }
+        Expression::Call { name, args } => format!("{name}({})", format_list(args)),
+        Expression::Spread(items) => format!("...{}", format_expression(items)),
+        Expression::Match { scrutinee, arms, default } => {
+            let mut arms: Vec<String> = arms
+                .iter()
+                .map(|(value, result)| {
+                    format!("{} => {}", format_expression(value), format_expression(result))
+                })
+                .collect();
+            if let Some(default) = default {
+                arms.push(format!("_ => {}", format_expression(default)));
+            }
+            let scrutinee = format_expression(scrutinee);
+            if arms.is_empty() {
+                format!("match ({scrutinee}) {{}}")
+            } else {
+                format!("match ({scrutinee}) {{ {} }}", arms.join(", "))
+            }
+        }
+        Expression::Property { object, key, optional } => {
+            let dot = if *optional { "?." } else { "." };
+            match **object {
+                Expression::Binary { .. } | Expression::Unary { .. } => {
+                    format!("({}){dot}{key}", format_expression(object))
+                }
+                _ => format!("{}{dot}{key}", format_expression(object)),
+            }
+        }
+        Expression::Unary { op, operand } => {
+            let text = format_expression(operand);
+            // Parenthesize operators and signed operands, so `-(a + b)` and `-(-x)` keep their meaning
+            if matches!(**operand, Expression::Binary { .. }) || text.starts_with(['-', '+']) {
+                format!("{op}({text})")
+            } else {
+                format!("{op}{text}")
+            }
+        }
+        Expression::Binary { .. } => format_operator_chain(expr),
+    }
+}
+
+/// Renders an operator chain such as `a + b - c`. Long chains nest as deeply as
+/// they have operators, so the left operands are walked in a loop rather than
+/// recursively.
+fn format_operator_chain(expr: &Expression) -> String {
+    // Operators are left-associative and comparisons do not chain
+    let needs_parens = |operand: &Expression, level: u8, right_side: bool| match operand {
+        Expression::Binary { op: inner, .. } => {
+            precedence(*inner) < level
+                || (precedence(*inner) == level && (right_side || level == COMPARISON))
+        }
+        _ => false,
+    };
+    let wrap = |operand: &Expression, level: u8, right_side: bool| {
+        let text = format_expression(operand);
+        if needs_parens(operand, level, right_side) {
+            format!("({text})")
+        } else {
+            text
+        }
+    };
+
+    let mut links = Vec::new();
+    let mut base = expr;
+    while let Expression::Binary { op, left, right } = base {
+        links.push((*op, right));
+        base = left;
+        if needs_parens(left, precedence(*op), false) {
+            break;
+        }
+    }
+
+    let innermost = links.last().map_or(0, |(op, _)| precedence(*op));
+    let mut text = wrap(base, innermost, false);
+    for (op, right) in links.into_iter().rev() {
+        let _ = write!(text, " {op} {}", wrap(right, precedence(op), true));
+    }
+    text
+}
@@ -2,6 +2,11 @@
 //!
 //! This module provides built-in graph generators for creating common graph topologies.
 //! Generators are invoked using the `generate` statement in GGL programs.
+//!
+//! Besides their own parameters, all generators accept optional `node_attrs` and
+//! `edge_attrs` objects whose entries are copied into the metadata of every
+//! generated node and edge respectively, and optional `max_nodes` / `max_edges`
+//! integers that make the generator fail instead of producing a larger graph.
 
 use crate::types::{Edge, Graph, Node};
 use serde_json::Value;
@@ -20,10 +25,179 @@ pub fn get_generator(name: &str) -> Option<GeneratorFn> {
         "star" => Some(generate_star),
         "tree" => Some(generate_tree),
         "barabasi_albert" => Some(generate_barabasi_albert),
+        "ring_lattice" => Some(generate_ring_lattice),
+        "complete_bipartite" => Some(generate_complete_bipartite),
+        "petersen" => Some(generate_petersen),
+        "named" => Some(generate_named),
         _ => None,
     }
 }
 
+/// Describes a generator parameter, for listings such as `ggl generators`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParamInfo {
+    pub name: &'static str,
+    /// Expected value type: `int`, `string` or `bool`.
+    pub kind: &'static str,
+    pub required: bool,
+    /// Default value of an optional parameter, as GGL source. `None` if the
+    /// default is computed or the parameter is simply left out.
+    pub default: Option<&'static str>,
+    pub description: &'static str,
+}
+
+/// Describes a generator and the parameters it accepts, besides the `node_attrs`,
+/// `edge_attrs`, `max_nodes` and `max_edges` parameters shared by all generators.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GeneratorInfo {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub params: &'static [ParamInfo],
+}
+
+const fn required(name: &'static str, kind: &'static str, description: &'static str) -> ParamInfo {
+    ParamInfo {
+        name,
+        kind,
+        required: true,
+        default: None,
+        description,
+    }
+}
+
+const fn optional(
+    name: &'static str,
+    kind: &'static str,
+    default: Option<&'static str>,
+    description: &'static str,
+) -> ParamInfo {
+    ParamInfo {
+        name,
+        kind,
+        required: false,
+        default,
+        description,
+    }
+}
+
+const PREFIX: ParamInfo = optional("prefix", "string", Some("\"n\""), "Prefix for node IDs");
+
+/// Every generator [`get_generator`] knows, in listing order.
+pub const GENERATORS: &[GeneratorInfo] = &[
+    GeneratorInfo {
+        name: "complete",
+        description: "Complete graph (clique)",
+        params: &[
+            required("nodes", "int", "Number of nodes"),
+            PREFIX,
+            optional("directed", "bool", Some("false"), "Generate directed edges"),
+        ],
+    },
+    GeneratorInfo {
+        name: "path",
+        description: "Path graph",
+        params: &[
+            required("nodes", "int", "Number of nodes in the path"),
+            PREFIX,
+            optional("directed", "bool", Some("false"), "Edges follow the path order"),
+        ],
+    },
+    GeneratorInfo {
+        name: "cycle",
+        description: "Cycle graph",
+        params: &[
+            required("nodes", "int", "Number of nodes in the cycle"),
+            PREFIX,
+            optional("directed", "bool", Some("false"), "Edges form a directed cycle"),
+        ],
+    },
+    GeneratorInfo {
+        name: "grid",
+        description: "2D grid graph",
+        params: &[
+            required("rows", "int", "Number of rows"),
+            required("cols", "int", "Number of columns"),
+            PREFIX,
+            optional("periodic", "bool", Some("false"), "Wrap edges around (torus)"),
+            optional("directed", "bool", Some("false"), "Edges point right and down"),
+        ],
+    },
+    GeneratorInfo {
+        name: "star",
+        description: "Star graph",
+        params: &[
+            required("nodes", "int", "Total number of nodes (1 center + N-1 spokes)"),
+            PREFIX,
+            optional("directed", "bool", Some("false"), "Edges point from center to spokes"),
+        ],
+    },
+    GeneratorInfo {
+        name: "tree",
+        description: "Balanced tree",
+        params: &[
+            required("branching", "int", "Branching factor"),
+            required("depth", "int", "Depth of the tree"),
+            PREFIX,
+            optional("directed", "bool", Some("true"), "Edges point from parent to child"),
+        ],
+    },
+    GeneratorInfo {
+        name: "barabasi_albert",
+        description: "Scale-free graph using the Barabási-Albert model",
+        params: &[
+            required("nodes", "int", "Final number of nodes"),
+            required("edges_per_node", "int", "Edges attached from each new node"),
+            optional(
+                "initial_nodes",
+                "int",
+                None,
+                "Size of the seed clique, at least edges_per_node (default: edges_per_node)",
+            ),
+            PREFIX,
+            optional("directed", "bool", Some("false"), "Attachment edges point from the new node"),
+            optional("seed", "int", None, "Seed for reproducible output"),
+        ],
+    },
+    GeneratorInfo {
+        name: "ring_lattice",
+        description: "Regular ring lattice",
+        params: &[
+            required("nodes", "int", "Number of nodes in the ring"),
+            required("k", "int", "Degree of every node, even and less than nodes"),
+            PREFIX,
+            optional("directed", "bool", Some("false"), "Edges point clockwise"),
+        ],
+    },
+    GeneratorInfo {
+        name: "complete_bipartite",
+        description: "Complete bipartite graph K_{left,right}",
+        params: &[
+            required("left", "int", "Number of nodes in the left partition"),
+            required("right", "int", "Number of nodes in the right partition"),
+            optional("left_prefix", "string", Some("\"l\""), "Prefix for left node IDs"),
+            optional("right_prefix", "string", Some("\"r\""), "Prefix for right node IDs"),
+            optional("directed", "bool", Some("false"), "Edges point from left to right"),
+        ],
+    },
+    GeneratorInfo {
+        name: "petersen",
+        description: "Petersen graph: 10 nodes, 15 edges, 3-regular",
+        params: &[
+            PREFIX,
+            optional("directed", "bool", Some("false"), "Generate directed edges"),
+        ],
+    },
+    GeneratorInfo {
+        name: "named",
+        description: "Well-known graph by name",
+        params: &[
+            required("name", "string", "Name of the graph. Supported: \"petersen\""),
+            PREFIX,
+            optional("directed", "bool", Some("false"), "Generate directed edges"),
+        ],
+    },
+];
+
 // --- Helper Functions ---
 
 fn get_param_int(params: &HashMap<String, Value>, key: &str) -> Result<usize, String> {
@@ -49,6 +223,30 @@ fn get_param_string(
         .unwrap_or_else(|| default.to_string())
 }
 
+fn get_param_object<'a>(
+    params: &'a HashMap<String, Value>,
+    key: &str,
+) -> Result<Option<&'a serde_json::Map<String, Value>>, String> {
+    match params.get(key) {
+        None => Ok(None),
+        Some(v) => v
+            .as_object()
+            .map(Some)
+            .ok_or_else(|| format!("Invalid object for parameter '{key}'")),
+    }
+}
+
+/// Returns a generator seeded from the `seed` parameter, or from entropy if it is absent.
+fn get_param_rng(params: &HashMap<String, Value>) -> Result<fastrand::Rng, String> {
+    match params.get("seed") {
+        None => Ok(fastrand::Rng::new()),
+        Some(v) => v
+            .as_u64()
+            .map(fastrand::Rng::with_seed)
+            .ok_or_else(|| "Invalid integer for parameter 'seed'".to_string()),
+    }
+}
+
 fn get_param_bool(params: &HashMap<String, Value>, key: &str, default: bool) -> bool {
     params
         .get(key)
@@ -56,6 +254,62 @@ fn get_param_bool(params: &HashMap<String, Value>, key: &str, default: bool) ->
         .unwrap_or(default)
 }
 
+/// Fails if a generator is about to create more nodes or edges than the
+/// `max_nodes` / `max_edges` parameters allow, before anything is allocated.
+pub(crate) fn check_size_limits(
+    params: &HashMap<String, Value>,
+    nodes: usize,
+    edges: usize,
+) -> Result<(), String> {
+    for (key, count, kind) in [("max_nodes", nodes, "nodes"), ("max_edges", edges, "edges")] {
+        if let Some(max) = params.get(key).and_then(Value::as_u64) {
+            if count as u64 > max {
+                return Err(format!(
+                    "Generator would create {count} {kind}, exceeding the limit of {max}"
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Copies the `node_attrs` / `edge_attrs` parameter maps into every node / edge of the graph.
+/// An edge `weight` must be a number and is set through [`Edge::with_weight`].
+fn apply_metadata_templates(
+    mut graph: Graph,
+    params: &HashMap<String, Value>,
+) -> Result<Graph, String> {
+    if let Some(attrs) = get_param_object(params, "node_attrs")? {
+        for node in graph.nodes.values_mut() {
+            node.metadata
+                .extend(attrs.iter().map(|(k, v)| (k.clone(), v.clone())));
+        }
+    }
+    if let Some(attrs) = get_param_object(params, "edge_attrs")? {
+        let weight = attrs
+            .get("weight")
+            .map(|value| {
+                value
+                    .as_f64()
+                    .ok_or_else(|| format!("Edge weight must be a number, got {value}"))
+            })
+            .transpose()?;
+        for (id, mut edge) in std::mem::take(&mut graph.edges) {
+            edge.metadata.extend(
+                attrs
+                    .iter()
+                    .filter(|(k, _)| k.as_str() != "weight")
+                    .map(|(k, v)| (k.clone(), v.clone())),
+            );
+            if let Some(weight) = weight {
+                edge = edge.with_weight(weight)?;
+            }
+            graph.edges.insert(id, edge);
+        }
+    }
+    Ok(graph)
+}
+
 // --- Generator Implementations ---
 
 /// Generates a complete graph (clique).
@@ -67,6 +321,8 @@ pub fn generate_complete(params: &HashMap<String, Value>) -> Result<Graph, Strin
     let n = get_param_int(params, "nodes")?;
     let prefix = get_param_string(params, "prefix", "n");
     let directed = get_param_bool(params, "directed", false);
+    let edge_count = n.saturating_mul(n.saturating_sub(1)) / if directed { 1 } else { 2 };
+    check_size_limits(params, n, edge_count)?;
 
     let mut graph = Graph::new();
     let nodes: Vec<_> = (0..n).map(|i| format!("{prefix}{i}")).collect();
@@ -92,7 +348,7 @@ pub fn generate_complete(params: &HashMap<String, Value>) -> Result<Graph, Strin
             );
         }
     }
-    Ok(graph)
+    apply_metadata_templates(graph, params)
 }
 
 /// Generates a path graph.
@@ -104,10 +360,11 @@ pub fn generate_path(params: &HashMap<String, Value>) -> Result<Graph, String> {
     let n = get_param_int(params, "nodes")?;
     let prefix = get_param_string(params, "prefix", "n");
     let directed = get_param_bool(params, "directed", false);
+    check_size_limits(params, n, n.saturating_sub(1))?;
     let mut graph = Graph::new();
 
     if n == 0 {
-        return Ok(graph);
+        return apply_metadata_templates(graph, params);
     }
 
     for i in 0..n {
@@ -119,7 +376,7 @@ pub fn generate_path(params: &HashMap<String, Value>) -> Result<Graph, String> {
         let edge_id = format!("e{i}_{}", i + 1);
         graph.add_edge(edge_id, Edge::new(source, target, directed));
     }
-    Ok(graph)
+    apply_metadata_templates(graph, params)
 }
 
 /// Generates a cycle graph.
@@ -131,10 +388,11 @@ pub fn generate_cycle(params: &HashMap<String, Value>) -> Result<Graph, String>
     let n = get_param_int(params, "nodes")?;
     let prefix = get_param_string(params, "prefix", "n");
     let directed = get_param_bool(params, "directed", false);
+    check_size_limits(params, n, n)?;
     let mut graph = Graph::new();
 
     if n == 0 {
-        return Ok(graph);
+        return apply_metadata_templates(graph, params);
     }
 
     for i in 0..n {
@@ -146,7 +404,7 @@ pub fn generate_cycle(params: &HashMap<String, Value>) -> Result<Graph, String>
         let edge_id = format!("e{}_{}", i, (i + 1) % n);
         graph.add_edge(edge_id, Edge::new(source, target, directed));
     }
-    Ok(graph)
+    apply_metadata_templates(graph, params)
 }
 
 /// Generates a 2D grid graph.
@@ -156,6 +414,7 @@ pub fn generate_cycle(params: &HashMap<String, Value>) -> Result<Graph, String>
 /// * `cols` (int, required): Number of columns in the grid.
 /// * `prefix` (string, optional): Prefix for node IDs. Default: "n".
 /// * `periodic` (bool, optional): If true, wraps edges around (torus). Default: false.
+/// * `directed` (bool, optional): If true, edges point right and down. Default: false.
 ///
 /// # Hard Example: Torus
 ///
@@ -195,6 +454,15 @@ pub fn generate_grid(params: &HashMap<String, Value>) -> Result<Graph, String> {
     let cols = get_param_int(params, "cols")?;
     let prefix = get_param_string(params, "prefix", "n");
     let periodic = get_param_bool(params, "periodic", false);
+    let directed = get_param_bool(params, "directed", false);
+    let cells = rows.saturating_mul(cols);
+    let edges = if periodic {
+        cells.saturating_mul(2)
+    } else {
+        rows.saturating_mul(cols.saturating_sub(1))
+            .saturating_add(rows.saturating_sub(1).saturating_mul(cols))
+    };
+    check_size_limits(params, cells, edges)?;
     let mut graph = Graph::new();
 
     for r in 0..rows {
@@ -211,18 +479,18 @@ pub fn generate_grid(params: &HashMap<String, Value>) -> Result<Graph, String> {
                 let target_c = (c + 1) % cols;
                 let target = format!("{prefix}{r}_{target_c}");
                 let edge_id = format!("eh_{r}_{c}");
-                graph.add_edge(edge_id, Edge::new(source.clone(), target, false));
+                graph.add_edge(edge_id, Edge::new(source.clone(), target, directed));
             }
             // Vertical connection
             if r < rows - 1 || periodic {
                 let target_r = (r + 1) % rows;
                 let target = format!("{prefix}{target_r}_{c}");
                 let edge_id = format!("ev_{r}_{c}");
-                graph.add_edge(edge_id, Edge::new(source.clone(), target, false));
+                graph.add_edge(edge_id, Edge::new(source.clone(), target, directed));
             }
         }
     }
-    Ok(graph)
+    apply_metadata_templates(graph, params)
 }
 
 /// Generates a star graph.
@@ -234,10 +502,11 @@ pub fn generate_star(params: &HashMap<String, Value>) -> Result<Graph, String> {
     let n = get_param_int(params, "nodes")?;
     let prefix = get_param_string(params, "prefix", "n");
     let directed = get_param_bool(params, "directed", false);
+    check_size_limits(params, n, n.saturating_sub(1))?;
     let mut graph = Graph::new();
 
     if n == 0 {
-        return Ok(graph);
+        return apply_metadata_templates(graph, params);
     }
 
     let center_id = format!("{prefix}0");
@@ -250,7 +519,7 @@ pub fn generate_star(params: &HashMap<String, Value>) -> Result<Graph, String> {
         let edge_id = format!("e_center_{i}");
         graph.add_edge(edge_id, Edge::new(source, target, directed));
     }
-    Ok(graph)
+    apply_metadata_templates(graph, params)
 }
 
 /// Generates a balanced tree.
@@ -258,17 +527,23 @@ pub fn generate_star(params: &HashMap<String, Value>) -> Result<Graph, String> {
 /// * `branching` (int, required): The branching factor of the tree.
 /// * `depth` (int, required): The depth of the tree.
 /// * `prefix` (string, optional): Prefix for node IDs. Default: "n".
+/// * `directed` (bool, optional): If true, edges point from parent to child. Default: true.
 pub fn generate_tree(params: &HashMap<String, Value>) -> Result<Graph, String> {
     let branching = get_param_int(params, "branching")?;
     let depth = get_param_int(params, "depth")?;
     let prefix = get_param_string(params, "prefix", "n");
+    let directed = get_param_bool(params, "directed", true);
+    let node_count = (1..depth).fold(1usize, |total, d| {
+        total.saturating_add(branching.saturating_pow(d as u32))
+    });
+    check_size_limits(params, node_count, node_count - 1)?;
     let mut graph = Graph::new();
 
     // Always create at least the root node
     graph.add_node(format!("{prefix}0"), Node::new());
 
     if depth <= 1 {
-        return Ok(graph);
+        return apply_metadata_templates(graph, params);
     }
 
     let mut parent_queue = vec![0];
@@ -282,27 +557,37 @@ pub fn generate_tree(params: &HashMap<String, Value>) -> Result<Graph, String> {
                 let child_id = format!("{prefix}{id_counter}");
                 graph.add_node(child_id.clone(), Node::new());
                 let edge_id = format!("e{parent_id_val}_{id_counter}");
-                graph.add_edge(edge_id, Edge::new(parent_id, child_id, true));
+                graph.add_edge(edge_id, Edge::new(parent_id, child_id, directed));
                 next_level_parents.push(id_counter);
                 id_counter += 1;
             }
         }
         parent_queue = next_level_parents;
     }
-    Ok(graph)
+    apply_metadata_templates(graph, params)
 }
 
 /// Generates a scale-free graph using the Barabási-Albert model.
 /// # Parameters
 /// * `nodes` (int, required): The final number of nodes in the graph.
 /// * `edges_per_node` (int, required): Number of edges to attach from a new node to existing nodes.
+/// * `initial_nodes` (int, optional): Size of the fully connected seed clique. Must be at least
+///   `edges_per_node`. Default: `edges_per_node`.
 /// * `prefix` (string, optional): Prefix for node IDs. Default: "n".
+/// * `directed` (bool, optional): If true, attachment edges point from the new node. Default: false.
+/// * `seed` (int, optional): Seed for the random number generator, for reproducible output.
 pub fn generate_barabasi_albert(
     params: &HashMap<String, Value>,
 ) -> Result<Graph, String> {
     let n = get_param_int(params, "nodes")?;
     let m = get_param_int(params, "edges_per_node")?;
+    let m0 = match params.get("initial_nodes") {
+        Some(_) => get_param_int(params, "initial_nodes")?,
+        None => m,
+    };
     let prefix = get_param_string(params, "prefix", "n");
+    let directed = get_param_bool(params, "directed", false);
+    let mut rng = get_param_rng(params)?;
 
     if m == 0 || n == 0 {
         return Ok(Graph::new());
@@ -310,66 +595,75 @@ pub fn generate_barabasi_albert(
     if m >= n {
         return Err("Parameter 'edges_per_node' must be less than 'nodes'".to_string());
     }
+    if m > m0 {
+        return Err("Parameter 'edges_per_node' must not exceed 'initial_nodes'".to_string());
+    }
+    if m0 > n {
+        return Err("Parameter 'initial_nodes' must not exceed 'nodes'".to_string());
+    }
+    let clique_edges = m0.saturating_mul(m0 - 1) / 2;
+    check_size_limits(params, n, clique_edges.saturating_add((n - m0).saturating_mul(m)))?;
 
     let mut graph = Graph::new();
 
-    // Start with m nodes and create a complete graph among them
-    for i in 0..m {
+    // Start with m0 nodes and create a complete graph among them
+    for i in 0..m0 {
         graph.add_node(format!("{prefix}{i}"), Node::new());
     }
 
-    // Create complete graph among initial m nodes
-    for i in 0..m {
-        for j in i + 1..m {
+    // Create complete graph among initial m0 nodes
+    for i in 0..m0 {
+        for j in i + 1..m0 {
             let edge_id = format!("e{i}_{j}");
             graph.add_edge(
                 edge_id,
-                Edge::new(format!("{prefix}{i}"), format!("{prefix}{j}"), false),
+                Edge::new(format!("{prefix}{i}"), format!("{prefix}{j}"), directed),
             );
         }
     }
 
-    // Initialize degree list for preferential attachment
-    let mut degrees: Vec<String> = graph
-        .edges
-        .values()
-        .flat_map(|e| vec![e.source.clone(), e.target.clone()])
+    // Initialize degree list for preferential attachment: every clique node has degree m0 - 1
+    let mut degrees: Vec<String> = (0..m0)
+        .flat_map(|i| std::iter::repeat_n(format!("{prefix}{i}"), m0 - 1))
         .collect();
 
-    // Add remaining n - m nodes
-    for i in m..n {
+    // Add remaining n - m0 nodes
+    for i in m0..n {
         let new_node_id = format!("{prefix}{i}");
         graph.add_node(new_node_id.clone(), Node::new());
 
-        // Select m unique targets based on preferential attachment
-        let mut selected_targets = std::collections::HashSet::new();
+        // Select m unique targets based on preferential attachment, in selection
+        // order so that a seeded run is reproducible
+        let mut selected_targets: Vec<String> = Vec::with_capacity(m);
         let mut attempts = 0;
 
         while selected_targets.len() < m && attempts < 100 {
             if !degrees.is_empty() {
-                let idx = fastrand::usize(..degrees.len());
-                selected_targets.insert(degrees[idx].clone());
+                let candidate = &degrees[rng.usize(..degrees.len())];
+                if !selected_targets.contains(candidate) {
+                    selected_targets.push(candidate.clone());
+                }
             }
             attempts += 1;
         }
 
         // If we couldn't get enough unique targets, fill with any available nodes
-        if selected_targets.len() < m {
-            for j in 0..i {
-                let node_id = format!("{prefix}{j}");
-                selected_targets.insert(node_id);
-                if selected_targets.len() >= m {
-                    break;
-                }
+        for j in 0..i {
+            if selected_targets.len() >= m {
+                break;
+            }
+            let node_id = format!("{prefix}{j}");
+            if !selected_targets.contains(&node_id) {
+                selected_targets.push(node_id);
             }
         }
 
         // Create edges to selected targets
-        for target_id in selected_targets.iter().take(m) {
+        for target_id in &selected_targets {
             let edge_id = format!("e{i}_{}", target_id.strip_prefix(&prefix).unwrap_or("?"));
             graph.add_edge(
                 edge_id,
-                Edge::new(new_node_id.clone(), target_id.clone(), false),
+                Edge::new(new_node_id.clone(), target_id.clone(), directed),
             );
             // Add both endpoints to degree list for future preferential attachment
             degrees.push(new_node_id.clone());
@@ -377,5 +671,135 @@ pub fn generate_barabasi_albert(
         }
     }
 
-    Ok(graph)
+    apply_metadata_templates(graph, params)
+}
+
+/// Generates a regular ring lattice, the starting point of small-world models.
+/// Each node is connected to its `k` nearest neighbors, `k / 2` on each side, wrapping around.
+/// # Parameters
+/// * `nodes` (int, required): Number of nodes in the ring.
+/// * `k` (int, required): Degree of every node. Must be even and less than `nodes`.
+/// * `prefix` (string, optional): Prefix for node IDs. Default: "n".
+/// * `directed` (bool, optional): If true, edges point clockwise. Default: false.
+pub fn generate_ring_lattice(params: &HashMap<String, Value>) -> Result<Graph, String> {
+    let n = get_param_int(params, "nodes")?;
+    let k = get_param_int(params, "k")?;
+    let prefix = get_param_string(params, "prefix", "n");
+    let directed = get_param_bool(params, "directed", false);
+
+    if k % 2 != 0 {
+        return Err("Parameter 'k' must be even".to_string());
+    }
+    if n > 0 && k >= n {
+        return Err("Parameter 'k' must be less than 'nodes'".to_string());
+    }
+    check_size_limits(params, n, n.saturating_mul(k / 2))?;
+
+    let mut graph = Graph::new();
+    for i in 0..n {
+        graph.add_node(format!("{prefix}{i}"), Node::new());
+    }
+    for i in 0..n {
+        for offset in 1..=k / 2 {
+            let j = (i + offset) % n;
+            let edge_id = format!("e{i}_{j}");
+            graph.add_edge(
+                edge_id,
+                Edge::new(format!("{prefix}{i}"), format!("{prefix}{j}"), directed),
+            );
+        }
+    }
+    apply_metadata_templates(graph, params)
+}
+
+/// Generates a complete bipartite graph `K_{left,right}`.
+/// Every node is tagged with a `partition` metadata entry of `"left"` or `"right"`.
+/// # Parameters
+/// * `left` (int, required): Number of nodes in the left partition.
+/// * `right` (int, required): Number of nodes in the right partition.
+/// * `left_prefix` (string, optional): Prefix for left node IDs. Default: "l".
+/// * `right_prefix` (string, optional): Prefix for right node IDs. Default: "r".
+/// * `directed` (bool, optional): If true, edges point from left to right. Default: false.
+pub fn generate_complete_bipartite(params: &HashMap<String, Value>) -> Result<Graph, String> {
+    let left = get_param_int(params, "left")?;
+    let right = get_param_int(params, "right")?;
+    let left_prefix = get_param_string(params, "left_prefix", "l");
+    let right_prefix = get_param_string(params, "right_prefix", "r");
+    let directed = get_param_bool(params, "directed", false);
+
+    if left_prefix == right_prefix {
+        return Err("Parameters 'left_prefix' and 'right_prefix' must differ".to_string());
+    }
+    check_size_limits(params, left.saturating_add(right), left.saturating_mul(right))?;
+
+    let mut graph = Graph::new();
+    for i in 0..left {
+        graph.add_node(
+            format!("{left_prefix}{i}"),
+            Node::new().with_metadata("partition".to_string(), Value::from("left")),
+        );
+    }
+    for j in 0..right {
+        graph.add_node(
+            format!("{right_prefix}{j}"),
+            Node::new().with_metadata("partition".to_string(), Value::from("right")),
+        );
+    }
+    for i in 0..left {
+        for j in 0..right {
+            let source = format!("{left_prefix}{i}");
+            let target = format!("{right_prefix}{j}");
+            let edge_id = format!("e_{source}_{target}");
+            graph.add_edge(edge_id, Edge::new(source, target, directed));
+        }
+    }
+    apply_metadata_templates(graph, params)
+}
+
+/// Generates the Petersen graph: 10 nodes, 15 edges, 3-regular.
+/// Nodes `0..5` form the outer cycle, nodes `5..10` the inner pentagram.
+/// # Parameters
+/// * `prefix` (string, optional): Prefix for node IDs. Default: "n".
+/// * `directed` (bool, optional): If true, generates directed edges. Default: false.
+pub fn generate_petersen(params: &HashMap<String, Value>) -> Result<Graph, String> {
+    let prefix = get_param_string(params, "prefix", "n");
+    let directed = get_param_bool(params, "directed", false);
+    check_size_limits(params, 10, 15)?;
+    let mut graph = Graph::new();
+
+    for i in 0..10 {
+        graph.add_node(format!("{prefix}{i}"), Node::new());
+    }
+
+    let mut pairs = Vec::new();
+    for i in 0..5 {
+        pairs.push((i, (i + 1) % 5)); // Outer cycle
+        pairs.push((i, i + 5)); // Spokes
+        pairs.push((i + 5, (i + 2) % 5 + 5)); // Inner pentagram
+    }
+    for (a, b) in pairs {
+        let edge_id = format!("e{a}_{b}");
+        graph.add_edge(
+            edge_id,
+            Edge::new(format!("{prefix}{a}"), format!("{prefix}{b}"), directed),
+        );
+    }
+    apply_metadata_templates(graph, params)
+}
+
+/// Generates a well-known graph by name.
+/// # Parameters
+/// * `name` (string, required): Name of the graph. Supported: "petersen".
+/// * `prefix` (string, optional): Prefix for node IDs. Default: "n".
+/// * `directed` (bool, optional): If true, generates directed edges. Default: false.
+pub fn generate_named(params: &HashMap<String, Value>) -> Result<Graph, String> {
+    let name = params
+        .get("name")
+        .ok_or_else(|| "Missing required parameter: 'name'".to_string())?
+        .as_str()
+        .ok_or_else(|| "Invalid string for parameter 'name'".to_string())?;
+    match name {
+        "petersen" => generate_petersen(params),
+        _ => Err(format!("Unknown named graph: '{name}'")),
+    }
 }
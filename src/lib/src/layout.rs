@@ -0,0 +1,263 @@
+//! # Graph Layouts
+//!
+//! This module assigns 2D coordinates to the nodes of a graph so that exported JSON
+//! can be drawn without computing a layout on the client. Each layout stores the
+//! position of a node in its `x` and `y` metadata entries, overwriting any previous
+//! values. Nodes are laid out in node ID order, so results are deterministic.
+
+use crate::types::Graph;
+use serde_json::Value;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+use std::f64::consts::PI;
+
+/// Settings shared by all layouts.
+#[derive(Debug, Clone)]
+pub struct LayoutOptions {
+    /// Side length of the square `[0, scale] x [0, scale]` the layout is placed in.
+    pub scale: f64,
+    /// Seed for layouts with a random component. Unseeded `random` layouts differ
+    /// between runs.
+    pub seed: Option<u64>,
+    /// Number of simulation steps for iterative layouts such as `force`.
+    pub iterations: usize,
+}
+
+impl Default for LayoutOptions {
+    fn default() -> Self {
+        LayoutOptions {
+            scale: 100.0,
+            seed: None,
+            iterations: 50,
+        }
+    }
+}
+
+/// Signature for a layout function.
+pub type LayoutFn = fn(&mut Graph, &LayoutOptions) -> Result<(), String>;
+
+/// Retrieves a layout function by name.
+pub fn get_layout(name: &str) -> Option<LayoutFn> {
+    match name {
+        "circle" => Some(layout_circle),
+        "grid" => Some(layout_grid),
+        "concentric" => Some(layout_concentric),
+        "random" => Some(layout_random),
+        "force" => Some(layout_force),
+        _ => None,
+    }
+}
+
+/// Applies the layout called `name` to the graph.
+pub fn apply_layout(graph: &mut Graph, name: &str, options: &LayoutOptions) -> Result<(), String> {
+    let layout = get_layout(name).ok_or_else(|| format!("Unknown layout: {name}"))?;
+    layout(graph, options)
+}
+
+// --- Helper Functions ---
+
+fn sorted_node_ids(graph: &Graph) -> Vec<String> {
+    let mut ids: Vec<String> = graph.nodes.keys().cloned().collect();
+    ids.sort();
+    ids
+}
+
+fn set_position(graph: &mut Graph, id: &str, x: f64, y: f64) -> Result<(), String> {
+    let node = graph
+        .get_node_mut(id)
+        .ok_or_else(|| format!("Node '{id}' not found"))?;
+    for (key, value) in [("x", x), ("y", y)] {
+        let value = serde_json::Number::from_f64(value)
+            .ok_or_else(|| format!("Non-finite coordinate for node '{id}'"))?;
+        node.metadata.insert(key.to_string(), Value::Number(value));
+    }
+    Ok(())
+}
+
+// --- Layout Implementations ---
+
+/// Places the nodes evenly on a circle of radius `scale / 2` centered in the layout area.
+pub fn layout_circle(graph: &mut Graph, options: &LayoutOptions) -> Result<(), String> {
+    let ids = sorted_node_ids(graph);
+    let radius = options.scale / 2.0;
+    let count = ids.len() as f64;
+    for (i, id) in ids.iter().enumerate() {
+        let angle = 2.0 * PI * i as f64 / count;
+        set_position(
+            graph,
+            id,
+            radius + radius * angle.cos(),
+            radius + radius * angle.sin(),
+        )?;
+    }
+    Ok(())
+}
+
+/// Places the nodes row by row on a square grid spanning the layout area.
+pub fn layout_grid(graph: &mut Graph, options: &LayoutOptions) -> Result<(), String> {
+    let ids = sorted_node_ids(graph);
+    let cols = (ids.len() as f64).sqrt().ceil() as usize;
+    let spacing = if cols > 1 {
+        options.scale / (cols - 1) as f64
+    } else {
+        0.0
+    };
+    for (i, id) in ids.iter().enumerate() {
+        set_position(
+            graph,
+            id,
+            (i % cols) as f64 * spacing,
+            (i / cols) as f64 * spacing,
+        )?;
+    }
+    Ok(())
+}
+
+/// Places the nodes on concentric circles by degree, highest degree innermost.
+///
+/// Nodes of equal degree share a ring and are spread evenly around it. The outermost
+/// ring has radius `scale / 2`; if the innermost ring holds a single node it is placed
+/// at the center.
+pub fn layout_concentric(graph: &mut Graph, options: &LayoutOptions) -> Result<(), String> {
+    let mut degrees: HashMap<String, usize> =
+        graph.nodes.keys().map(|id| (id.clone(), 0)).collect();
+    for edge in graph.edges.values() {
+        for endpoint in [&edge.source, &edge.target] {
+            if let Some(degree) = degrees.get_mut(endpoint) {
+                *degree += 1;
+            }
+        }
+    }
+
+    let mut rings: BTreeMap<Reverse<usize>, Vec<String>> = BTreeMap::new();
+    for id in sorted_node_ids(graph) {
+        rings.entry(Reverse(degrees[&id])).or_default().push(id);
+    }
+
+    let max_radius = options.scale / 2.0;
+    let ring_count = rings.len();
+    let centered = rings.values().next().is_some_and(|ring| ring.len() == 1);
+    for (r, ids) in rings.values().enumerate() {
+        let radius = if centered {
+            if ring_count > 1 {
+                max_radius * r as f64 / (ring_count - 1) as f64
+            } else {
+                0.0
+            }
+        } else {
+            max_radius * (r + 1) as f64 / ring_count as f64
+        };
+        for (i, id) in ids.iter().enumerate() {
+            let angle = 2.0 * PI * i as f64 / ids.len() as f64;
+            set_position(
+                graph,
+                id,
+                max_radius + radius * angle.cos(),
+                max_radius + radius * angle.sin(),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Places the nodes uniformly at random in the layout area.
+pub fn layout_random(graph: &mut Graph, options: &LayoutOptions) -> Result<(), String> {
+    let mut rng = match options.seed {
+        Some(seed) => fastrand::Rng::with_seed(seed),
+        None => fastrand::Rng::new(),
+    };
+    for id in sorted_node_ids(graph) {
+        let (x, y) = (rng.f64() * options.scale, rng.f64() * options.scale);
+        set_position(graph, &id, x, y)?;
+    }
+    Ok(())
+}
+
+/// Fruchterman–Reingold force-directed layout.
+///
+/// Nodes start from a random placement (seeded with `seed`, or 0 when unset, so the
+/// result is always reproducible) and then repel each other while edges pull their
+/// endpoints together, settling around the ideal edge length `sqrt(scale² / n)`.
+/// The maximum step size shrinks linearly to zero over `iterations` steps.
+pub fn layout_force(graph: &mut Graph, options: &LayoutOptions) -> Result<(), String> {
+    let ids = sorted_node_ids(graph);
+    if ids.is_empty() {
+        return Ok(());
+    }
+    let index: HashMap<&str, usize> = ids
+        .iter()
+        .enumerate()
+        .map(|(i, id)| (id.as_str(), i))
+        .collect();
+    let mut edges: Vec<(usize, usize)> = graph
+        .edges
+        .values()
+        .filter_map(|edge| {
+            Some((
+                *index.get(edge.source.as_str())?,
+                *index.get(edge.target.as_str())?,
+            ))
+        })
+        .filter(|(u, v)| u != v)
+        .collect();
+    edges.sort_unstable();
+
+    let mut rng = fastrand::Rng::with_seed(options.seed.unwrap_or(0));
+    let mut positions: Vec<(f64, f64)> = ids
+        .iter()
+        .map(|_| (rng.f64() * options.scale, rng.f64() * options.scale))
+        .collect();
+
+    let k = (options.scale * options.scale / ids.len() as f64).sqrt();
+    let initial_temperature = options.scale / 10.0;
+    for step in 0..options.iterations {
+        let mut displacement = vec![(0.0, 0.0); positions.len()];
+
+        // Repulsion between every pair of nodes
+        for i in 0..positions.len() {
+            for j in (i + 1)..positions.len() {
+                let (dx, dy) = (
+                    positions[i].0 - positions[j].0,
+                    positions[i].1 - positions[j].1,
+                );
+                let distance = (dx * dx + dy * dy).sqrt().max(0.01);
+                let force = k * k / distance;
+                let (fx, fy) = (dx / distance * force, dy / distance * force);
+                displacement[i].0 += fx;
+                displacement[i].1 += fy;
+                displacement[j].0 -= fx;
+                displacement[j].1 -= fy;
+            }
+        }
+
+        // Attraction along edges
+        for &(u, v) in &edges {
+            let (dx, dy) = (
+                positions[u].0 - positions[v].0,
+                positions[u].1 - positions[v].1,
+            );
+            let distance = (dx * dx + dy * dy).sqrt().max(0.01);
+            let force = distance * distance / k;
+            let (fx, fy) = (dx / distance * force, dy / distance * force);
+            displacement[u].0 -= fx;
+            displacement[u].1 -= fy;
+            displacement[v].0 += fx;
+            displacement[v].1 += fy;
+        }
+
+        let temperature = initial_temperature * (1.0 - step as f64 / options.iterations as f64);
+        for (position, (dx, dy)) in positions.iter_mut().zip(displacement) {
+            let length = (dx * dx + dy * dy).sqrt();
+            if length > 0.0 {
+                let step_length = length.min(temperature);
+                position.0 = (position.0 + dx / length * step_length).clamp(0.0, options.scale);
+                position.1 = (position.1 + dy / length * step_length).clamp(0.0, options.scale);
+            }
+        }
+    }
+
+    for (id, (x, y)) in ids.iter().zip(positions) {
+        set_position(graph, id, x, y)?;
+    }
+    Ok(())
+}
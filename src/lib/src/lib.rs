@@ -48,6 +48,7 @@
 //! * **Built-in Generators**: Create common graph structures (complete, path, cycle, grid, star, tree, scale-free).
 //! * **Transformation Rules**: Apply pattern-based rules to modify graph structure.
 //! * **Rich Attributes**: Support for typed nodes and edges with metadata.
+//! * **Layouts**: Assign `x`/`y` coordinates to nodes (circle, grid, concentric, random, force-directed).
 //! * **JSON Output**: Export graphs in standard JSON format.
 //!
 //! ## More Examples
@@ -102,21 +103,30 @@
 //! ```
 
 use std::collections::HashMap;
+use std::time::{Duration, Instant};
 
+pub mod format;
 pub mod generators;
+pub mod layout;
 pub mod parser;
 pub mod rules;
+pub mod schema;
 pub mod types;
 
 
 use crate::generators::get_generator;
 use crate::parser::{
-    ApplyStatement, EdgeDeclaration, Expression, ForStatement, GenerateStatement, LetStatement,
+    ApplyStatement, BinaryOp, EdgeDeclaration, Expression, ForIterable, ForStatement, GenerateStatement, LetStatement, UnaryOp, WhileStatement,
     NodeDeclaration, RuleDefinition, Statement,
 };
 use crate::parser::parse_ggl;
-use crate::types::{Edge, Graph, Node};
+use crate::types::{Edge, Graph, MergePolicy, Node};
 use serde_json::Value;
+use std::cmp::Ordering;
+
+/// Maximum number of iterations of a single `while` loop, guarding against
+/// conditions that never become false.
+pub const MAX_WHILE_ITERATIONS: usize = 10_000;
 
 /// The main GGL engine for parsing and executing GGL programs.
 ///
@@ -126,6 +136,54 @@ pub struct GGLEngine {
     pub graph: Graph,
     rules: HashMap<String, rules::Rule>,
     context: HashMap<String, Value>,
+    merge_policy: MergePolicy,
+    seed: Option<u64>,
+    rng: fastrand::Rng,
+    max_nodes: Option<usize>,
+    max_edges: Option<usize>,
+    strict_identifiers: bool,
+    float_precision: Option<usize>,
+    float_tolerance: FloatTolerance,
+    minify: bool,
+    profiling: bool,
+    timings: Timings,
+    tracing: bool,
+    trace: Vec<String>,
+    explaining: bool,
+    explanations: Vec<(String, Vec<rules::Match>)>,
+}
+
+/// How close two numbers must be for `==` and `!=` to treat them as equal.
+///
+/// Two numbers are equal if they differ by at most `absolute`, or by at most
+/// `relative` times the larger magnitude. The default of zero for both compares
+/// exactly.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FloatTolerance {
+    pub absolute: f64,
+    pub relative: f64,
+}
+
+impl FloatTolerance {
+    /// Returns true if `a` and `b` are equal within this tolerance.
+    pub fn approx_eq(&self, a: f64, b: f64) -> bool {
+        let difference = (a - b).abs();
+        a == b
+            || difference <= self.absolute
+            || difference <= self.relative * a.abs().max(b.abs())
+    }
+}
+
+/// Time spent in each phase of the last [`GGLEngine::generate_from_ggl`] call.
+///
+/// Only recorded when profiling is enabled with [`GGLEngine::with_profiling`].
+#[derive(Debug, Clone, Default)]
+pub struct Timings {
+    pub parse: Duration,
+    /// Executing statements, excluding rule application.
+    pub generation: Duration,
+    pub rules: Duration,
+    pub serialization: Duration,
 }
 
 impl Default for GGLEngine {
@@ -141,22 +199,200 @@ impl GGLEngine {
             graph: Graph::new(),
             rules: HashMap::new(),
             context: HashMap::new(),
+            merge_policy: MergePolicy::default(),
+            seed: None,
+            rng: fastrand::Rng::new(),
+            max_nodes: None,
+            max_edges: None,
+            strict_identifiers: false,
+            float_precision: None,
+            float_tolerance: FloatTolerance::default(),
+            minify: false,
+            profiling: false,
+            timings: Timings::default(),
+            tracing: false,
+            trace: Vec::new(),
+            explaining: false,
+            explanations: Vec::new(),
         }
     }
 
-    /// Parses and executes a GGL program, returning the resulting graph as JSON.
-    pub fn generate_from_ggl(&mut self, ggl_code: &str) -> Result<String, String> {
-        let ast = parse_ggl(ggl_code).map_err(|e| format!("Parse error: {e}"))?;
+    /// Records per-phase [`Timings`] on every run.
+    ///
+    /// Off by default since `Instant` is not available on every target (e.g. `wasm32`).
+    pub fn with_profiling(mut self, profiling: bool) -> Self {
+        self.profiling = profiling;
+        self
+    }
 
-        // Reset state for a new run
+    /// Returns the phase timings of the last run. All zero unless profiling is enabled.
+    pub fn timings(&self) -> &Timings {
+        &self.timings
+    }
+
+    /// Records every builtin call and the size of its result while evaluating.
+    pub fn with_trace(mut self, tracing: bool) -> Self {
+        self.tracing = tracing;
+        self
+    }
+
+    /// Returns the builtin calls of the last run in evaluation order, one line per
+    /// call such as `shuffle([3 items]) -> [3 items]`. Empty unless tracing is enabled.
+    pub fn trace(&self) -> &[String] {
+        &self.trace
+    }
+
+    /// Records the matches each `apply` statement's rule finds just before it
+    /// rewrites the graph.
+    pub fn with_rule_explanations(mut self, explaining: bool) -> Self {
+        self.explaining = explaining;
+        self
+    }
+
+    /// Starts a timer if profiling is enabled.
+    fn start_timer(&self) -> Option<Instant> {
+        self.profiling.then(Instant::now)
+    }
+
+    /// Fails generation once the graph would hold more than `max_nodes` nodes.
+    /// Generators check the limit before allocating their output.
+    pub fn with_max_nodes(mut self, max_nodes: usize) -> Self {
+        self.max_nodes = Some(max_nodes);
+        self
+    }
+
+    /// Fails generation once the graph would hold more than `max_edges` edges.
+    /// Generators check the limit before allocating their output.
+    pub fn with_max_edges(mut self, max_edges: usize) -> Self {
+        self.max_edges = Some(max_edges);
+        self
+    }
+
+    /// Makes randomized generators reproducible.
+    ///
+    /// Each `generate` statement without an explicit `seed` parameter receives one
+    /// derived from this seed, so the same program always produces the same graph.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self.rng = fastrand::Rng::with_seed(seed);
+        self
+    }
+
+    /// Makes unknown identifiers in value positions (attribute values, generator
+    /// parameters, `let` values) an error instead of a string literal, catching typos.
+    ///
+    /// Node IDs, types and edge endpoints are names and stay lenient either way.
+    pub fn with_strict_identifiers(mut self, strict: bool) -> Self {
+        self.strict_identifiers = strict;
+        self
+    }
+
+    /// Rounds floats in the JSON output to `digits` significant digits, hiding
+    /// rounding noise such as `0.30000000000000004`. Integers and the graph held
+    /// by the engine are left untouched. Off by default.
+    pub fn with_float_precision(mut self, digits: usize) -> Self {
+        self.float_precision = Some(digits);
+        self
+    }
+
+    /// Emits compact JSON without any whitespace instead of pretty-printing it.
+    pub fn with_minified_output(mut self, minify: bool) -> Self {
+        self.minify = minify;
+        self
+    }
+
+    /// Sets the tolerance `==` and `!=` use when comparing numbers, so values that
+    /// only differ by accumulated rounding error compare equal. Exact by default.
+    /// Two integers always compare exactly; the tolerance only applies when at
+    /// least one operand is a float.
+    pub fn with_float_tolerance(mut self, tolerance: FloatTolerance) -> Self {
+        self.float_tolerance = tolerance;
+        self
+    }
+
+    /// Sets how generator output is merged when its node or edge IDs collide with
+    /// existing ones. Defaults to [`MergePolicy::Overwrite`].
+    pub fn with_merge_policy(mut self, policy: MergePolicy) -> Self {
+        self.merge_policy = policy;
+        self
+    }
+
+    /// Clears the graph, rules and variables left by the previous run and reseeds
+    /// the generator, keeping the configuration.
+    ///
+    /// [`GGLEngine::generate_from_ggl`] calls this before every run, so one engine
+    /// can be reused for many programs without being rebuilt.
+    pub fn reset(&mut self) {
         self.graph = Graph::new();
         self.rules.clear();
         self.context.clear();
+        if let Some(seed) = self.seed {
+            self.rng = fastrand::Rng::with_seed(seed);
+        }
+    }
+
+    /// Parses and executes a GGL program, returning the resulting graph directly.
+    ///
+    /// This is [`GGLEngine::generate_from_ggl`] without the JSON serialization, for
+    /// Rust callers that work with the [`Graph`] itself.
+    pub fn build_graph(&mut self, ggl_code: &str) -> Result<&Graph, String> {
+        self.timings = Timings::default();
+        self.trace.clear();
+        self.explanations.clear();
+        let timer = self.start_timer();
+        let ast = parse_ggl(ggl_code).map_err(|e| format!("Parse error: {e}"))?;
+        if let Some(start) = timer {
+            self.timings.parse = start.elapsed();
+        }
+
+        self.reset();
+
+        let timer = self.start_timer();
+        self.execute_statements(&ast.statements)?;
+        if let Some(start) = timer {
+            self.timings.generation = start.elapsed().saturating_sub(self.timings.rules);
+        }
+        Ok(&self.graph)
+    }
 
+    /// Executes a GGL program on top of the current graph, rules and variables
+    /// instead of starting from an empty engine.
+    ///
+    /// Use this to build a graph in several steps, e.g. declaring nodes in one
+    /// program and connecting them in the next. Call [`GGLEngine::reset`] to start over.
+    pub fn apply_ggl(&mut self, ggl_code: &str) -> Result<&Graph, String> {
+        let ast = parse_ggl(ggl_code).map_err(|e| format!("Parse error: {e}"))?;
         self.execute_statements(&ast.statements)?;
+        Ok(&self.graph)
+    }
+
+    /// Parses and executes a GGL program, returning the resulting graph as JSON.
+    pub fn generate_from_ggl(&mut self, ggl_code: &str) -> Result<String, String> {
+        self.build_graph(ggl_code)?;
 
         // Serialize final graph to JSON
-        serde_json::to_string_pretty(&self.graph).map_err(|e| format!("Serialization error: {e}"))
+        let timer = self.start_timer();
+        let json = match self.float_precision {
+            Some(digits) => serde_json::to_value(&self.graph).and_then(|mut value| {
+                round_floats(&mut value, digits);
+                self.serialize(&value)
+            }),
+            None => self.serialize(&self.graph),
+        }
+        .map_err(|e| format!("Serialization error: {e}"))?;
+        if let Some(start) = timer {
+            self.timings.serialization = start.elapsed();
+        }
+        Ok(json)
+    }
+
+    /// Serializes `value` compactly or pretty-printed, depending on the engine settings.
+    fn serialize<T: serde::Serialize>(&self, value: &T) -> serde_json::Result<String> {
+        if self.minify {
+            serde_json::to_string(value)
+        } else {
+            serde_json::to_string_pretty(value)
+        }
     }
 
     /// Executes a sequence of GGL statements within the current context.
@@ -167,11 +403,17 @@ impl GGLEngine {
         Ok(())
     }
 
+    /// Checks the graph against the configured node and edge limits.
+    fn check_limits(&self) -> Result<(), String> {
+        check_graph_limits(&self.graph, self.max_nodes, self.max_edges)
+    }
+
     /// Executes a single GGL statement.
     fn execute_statement(&mut self, statement: &Statement) -> Result<(), String> {
         match statement {
             Statement::Let(stmt) => self.handle_let(stmt),
             Statement::For(stmt) => self.handle_for(stmt),
+            Statement::While(stmt) => self.handle_while(stmt),
             Statement::Node(stmt) => self.handle_node(stmt),
             Statement::Edge(stmt) => self.handle_edge(stmt),
             Statement::Generate(stmt) => self.handle_generate(stmt),
@@ -189,43 +431,80 @@ impl GGLEngine {
     }
 
     fn handle_for(&mut self, stmt: &ForStatement) -> Result<(), String> {
-        let start = self.evaluate_expression(&stmt.start)?.as_i64().ok_or("For loop start must be an integer")? as isize;
-        let end = self.evaluate_expression(&stmt.end)?.as_i64().ok_or("For loop end must be an integer")? as isize;
-
-        for i in start..end {
-            self.context
-                .insert(stmt.variable.clone(), Value::Number(serde_json::Number::from(i as i64)));
-            self.execute_statements(&stmt.body)?;
+        match &stmt.iterable {
+            ForIterable::Range { start, end } => {
+                let start = self.evaluate_expression(start)?.as_i64().ok_or("For loop start must be an integer")?;
+                let end = self.evaluate_expression(end)?.as_i64().ok_or("For loop end must be an integer")?;
+                for i in start..end {
+                    self.execute_loop_body(stmt, Value::from(i))?;
+                }
+            }
+            ForIterable::Items(expr) => match self.evaluate_expression(expr)? {
+                Value::Array(items) => {
+                    for item in items {
+                        self.execute_loop_body(stmt, item)?;
+                    }
+                }
+                other => return Err(format!("For loop can only iterate over a range or an array, got {other}")),
+            },
         }
         // Remove loop variable from context after loop finishes
         self.context.remove(&stmt.variable);
         Ok(())
     }
 
+    fn handle_while(&mut self, stmt: &WhileStatement) -> Result<(), String> {
+        let mut iterations = 0;
+        loop {
+            match self.evaluate_expression(&stmt.condition)? {
+                Value::Bool(true) => {}
+                Value::Bool(false) => return Ok(()),
+                other => return Err(format!("While condition must be a boolean, got {other}")),
+            }
+            if iterations == MAX_WHILE_ITERATIONS {
+                return Err(format!("While loop exceeded {MAX_WHILE_ITERATIONS} iterations"));
+            }
+            iterations += 1;
+            self.execute_statements(&stmt.body)?;
+        }
+    }
+
+    /// Runs one iteration of a `for` loop with the loop variable bound to `value`.
+    fn execute_loop_body(&mut self, stmt: &ForStatement, value: Value) -> Result<(), String> {
+        self.context.insert(stmt.variable.clone(), value);
+        self.execute_statements(&stmt.body)
+    }
+
     fn handle_node(&mut self, stmt: &NodeDeclaration) -> Result<(), String> {
-        let id = self.evaluate_expression(&stmt.id)?.to_string().replace('"', "");
+        let id = self.evaluate_name(&stmt.id)?;
         let node_type = match &stmt.node_type {
-            Some(expr) => self.evaluate_expression(expr)?.to_string().replace('"', ""),
+            Some(expr) => self.evaluate_name(expr)?,
             None => String::new(),
         };
         let mut metadata = HashMap::new();
+        if let Some(doc) = &stmt.doc {
+            metadata.insert("doc".to_string(), Value::String(doc.clone()));
+        }
         for (key, expr) in &stmt.attributes {
             metadata.insert(key.clone(), self.evaluate_expression(expr)?);
         }
 
         self.graph
             .add_node(id, Node::new().with_type(node_type).with_metadata_map(metadata));
-        Ok(())
+        self.check_limits()
     }
 
     fn handle_edge(&mut self, stmt: &EdgeDeclaration) -> Result<(), String> {
         let id = match &stmt.id {
-            Some(expr) => self.evaluate_expression(expr)?.to_string().replace('"', ""),
+            Some(expr) => self.evaluate_name(expr)?,
             None => self.graph.generate_unique_edge_id("edge"),
         };
-        let source = self.evaluate_expression(&stmt.source)?.to_string().replace('"', "");
-        let target = self.evaluate_expression(&stmt.target)?.to_string().replace('"', "");
+        let source = self.evaluate_name(&stmt.source)?;
+        let target = self.evaluate_name(&stmt.target)?;
         let mut metadata = HashMap::new();
+        if let Some(doc) = &stmt.doc {
+            metadata.insert("doc".to_string(), Value::String(doc.clone()));
+        }
         for (key, expr) in &stmt.attributes {
             metadata.insert(key.clone(), self.evaluate_expression(expr)?);
         }
@@ -234,7 +513,7 @@ impl GGLEngine {
             id,
             Edge::new(source, target, stmt.directed).with_metadata_map(metadata),
         );
-        Ok(())
+        self.check_limits()
     }
 
     fn handle_generate(&mut self, stmt: &GenerateStatement) -> Result<(), String> {
@@ -244,16 +523,26 @@ impl GGLEngine {
             for (key, expr) in &stmt.params {
                 params.insert(key.clone(), self.evaluate_expression(expr)?);
             }
+            // Let generators refuse oversized output up front; a stricter limit in the
+            // program itself still applies.
+            for (key, limit) in [("max_nodes", self.max_nodes), ("max_edges", self.max_edges)] {
+                if let Some(max) = limit {
+                    let current = params.get(key).and_then(Value::as_u64);
+                    if current.is_none_or(|c| c > max as u64) {
+                        params.insert(key.to_string(), Value::from(max));
+                    }
+                }
+            }
+            if self.seed.is_some() && !params.contains_key("seed") {
+                params.insert("seed".to_string(), Value::from(self.rng.u64(..)));
+            }
             let generated_graph =
                 generator(&params).map_err(|e| format!("Generator '{generator_name}' error: {e}"))?;
 
-            // Merge generated graph into the current graph
-            for (id, node) in generated_graph.nodes {
-                self.graph.add_node(id, node);
-            }
-            for (id, edge) in generated_graph.edges {
-                self.graph.add_edge(id, edge);
-            }
+            self.graph
+                .merge(generated_graph, self.merge_policy)
+                .map_err(|e| format!("Generator '{generator_name}' error: {e}"))?;
+            self.check_limits()?;
         } else {
             return Err(format!("Unknown generator: {generator_name}"));
         }
@@ -273,8 +562,19 @@ impl GGLEngine {
     fn handle_apply(&mut self, stmt: &ApplyStatement) -> Result<(), String> {
         let iterations = self.evaluate_expression(&stmt.iterations)?.as_i64().ok_or("Apply iterations must be an integer")? as usize;
         if let Some(rule) = self.rules.get(&stmt.rule_name).cloned() {
-            rule.apply(&mut self.graph, iterations)
-                .map_err(|e| format!("Rule '{}' application error: {e}", stmt.rule_name))?;
+            if self.explaining {
+                let matches = rule.find_matches(&self.graph)?;
+                self.explanations.push((stmt.rule_name.clone(), matches));
+            }
+            let timer = self.start_timer();
+            let (max_nodes, max_edges) = (self.max_nodes, self.max_edges);
+            rule.apply_with(&mut self.graph, iterations, |graph| {
+                check_graph_limits(graph, max_nodes, max_edges)
+            })
+            .map_err(|e| format!("Rule '{}' application error: {e}", stmt.rule_name))?;
+            if let Some(start) = timer {
+                self.timings.rules += start.elapsed();
+            }
         } else {
             return Err(format!("Unknown rule: {}", stmt.rule_name));
         }
@@ -286,19 +586,60 @@ impl GGLEngine {
         &self.graph
     }
 
+    /// Lists the rule of every `apply` statement of the last run, in execution
+    /// order, with the matches it found in the graph just before it was applied.
+    /// Empty unless enabled with [`GGLEngine::with_rule_explanations`].
+    pub fn explain_rules(&self) -> &[(String, Vec<rules::Match>)] {
+        &self.explanations
+    }
+
+    /// Checks the current graph against `schema`, returning every violation.
+    pub fn validate_against_schema(
+        &self,
+        schema: &schema::Schema,
+    ) -> Result<(), Vec<schema::SchemaViolation>> {
+        let violations = schema::validate(&self.graph, schema);
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+
+    /// Evaluates an expression in a name position (node ID or type, edge ID or
+    /// endpoint), where a bare identifier that is not a variable is the name itself.
+    fn evaluate_name(&mut self, expr: &Expression) -> Result<String, String> {
+        let value = match expr {
+            Expression::Identifier(name) => self
+                .context
+                .get(name)
+                .cloned()
+                .unwrap_or_else(|| Value::String(name.clone())),
+            _ => self.evaluate_expression(expr)?,
+        };
+        Ok(value.to_string().replace('"', ""))
+    }
+
     /// Evaluates an expression by resolving variables or interpreting literals.
-    fn evaluate_expression(&self, expr: &Expression) -> Result<Value, String> {
+    fn evaluate_expression(&mut self, expr: &Expression) -> Result<Value, String> {
         match expr {
             Expression::StringLiteral(s) => Ok(Value::String(s.clone())),
             Expression::Integer(i) => Ok(Value::Number(serde_json::Number::from(*i))),
-            Expression::Float(f) => Ok(Value::Number(serde_json::Number::from_f64(*f).unwrap())),
+            Expression::Float(f) => serde_json::Number::from_f64(*f)
+                .map(Value::Number)
+                .ok_or_else(|| format!("Non-finite number produced: {f}")),
             Expression::Boolean(b) => Ok(Value::Bool(*b)),
+            Expression::Null => Ok(Value::Null),
             Expression::Identifier(name) => {
                 // First try to resolve as a variable, if not found treat as string literal
-                Ok(self.context
-                    .get(name)
-                    .cloned()
-                    .unwrap_or_else(|| Value::String(name.clone())))
+                match self.context.get(name) {
+                    Some(value) => Ok(value.clone()),
+                    // Read-only graph statistics, unless shadowed by a variable
+                    None if name == "node_count" => Ok(Value::from(self.graph.nodes.len())),
+                    None if name == "edge_count" => Ok(Value::from(self.graph.edges.len())),
+                    None if self.strict_identifiers => Err(format!("undefined variable: {name}")),
+                    None => Ok(Value::String(name.clone())),
+                }
             }
             Expression::FormattedString(parts) => {
                 let mut result = String::new();
@@ -309,10 +650,635 @@ impl GGLEngine {
                             let value = self.context.get(var).ok_or(format!("Undefined variable: '{var}'"))?;
                             result.push_str(&value.to_string().replace('"', ""));
                         }
+                        parser::StringPart::Expression(expr) => {
+                            let value = self.evaluate_expression(expr)?;
+                            result.push_str(&value.to_string().replace('"', ""));
+                        }
                     }
                 }
                 Ok(Value::String(result))
             }
+            Expression::Array(items) => items
+                .iter()
+                .map(|item| self.evaluate_expression(item))
+                .collect::<Result<_, _>>()
+                .map(Value::Array),
+            Expression::Object(pairs) => pairs
+                .iter()
+                .map(|(key, value)| Ok((key.clone(), self.evaluate_expression(value)?)))
+                .collect::<Result<_, String>>()
+                .map(Value::Object),
+            Expression::Binary { .. } => self.evaluate_operator_chain(expr),
+            Expression::Match { .. } => self.evaluate_match(expr),
+            Expression::Wildcard => {
+                Err("Wildcard '*' is only allowed in rule LHS attributes".to_string())
+            }
+            Expression::Variable(name) => {
+                Err(format!("Attribute variable '${name}' is only allowed in rule attributes"))
+            }
+            Expression::Spread(_) => {
+                Err(format!("Spread '{expr}' is only allowed in function call arguments"))
+            }
+            Expression::Call { name, args } => {
+                let args = self.evaluate_arguments(name, args)?;
+                if !self.tracing {
+                    return self.evaluate_builtin_call(name, args);
+                }
+                let summary: Vec<String> = args.iter().map(describe_value).collect();
+                let result = self.evaluate_builtin_call(name, args)?;
+                self.trace.push(format!(
+                    "{name}({}) -> {}",
+                    summary.join(", "),
+                    describe_value(&result)
+                ));
+                Ok(result)
+            }
+            Expression::Property { .. } => self.evaluate_property_chain(expr),
+            Expression::Unary { op, operand } => {
+                let value = self.evaluate_expression(operand)?;
+                evaluate_unary(*op, &value)
+            }
+        }
+    }
+
+    /// Evaluates a left-nested operator chain such as `a + b + c`.
+    ///
+    /// Long chains nest as deeply as they have operators, so the left operands are
+    /// walked in a loop rather than recursively.
+    fn evaluate_operator_chain(&mut self, expr: &Expression) -> Result<Value, String> {
+        let mut operations = Vec::new();
+        let mut base = expr;
+        while let Expression::Binary { op, left, right } = base {
+            operations.push((*op, right));
+            base = left;
+        }
+
+        let mut value = self.evaluate_expression(base)?;
+        for (op, right) in operations.into_iter().rev() {
+            if op == BinaryOp::Coalesce && !value.is_null() {
+                continue;
+            }
+            let right = self.evaluate_expression(right)?;
+            value = match op {
+                BinaryOp::Eq | BinaryOp::Ne => {
+                    Value::Bool(self.values_equal(&value, &right)? == (op == BinaryOp::Eq))
+                }
+                _ => evaluate_binary(op, &value, &right)?,
+            };
+        }
+        Ok(value)
+    }
+
+    /// Evaluates a call's arguments, expanding each `...items` into its elements.
+    fn evaluate_arguments(&mut self, name: &str, args: &[Expression]) -> Result<Vec<Value>, String> {
+        let mut values = Vec::with_capacity(args.len());
+        for arg in args {
+            let Expression::Spread(items) = arg else {
+                values.push(self.evaluate_expression(arg)?);
+                continue;
+            };
+            match self.evaluate_expression(items)? {
+                Value::Array(items) => values.extend(items),
+                other => return Err(format!("{name} can only spread an array, got {other}")),
+            }
+        }
+        Ok(values)
+    }
+
+    /// Compares two values as `==` does, with floats equal within the tolerance.
+    fn values_equal(&self, left: &Value, right: &Value) -> Result<bool, String> {
+        let has_float = left.as_number().is_some_and(|n| n.is_f64())
+            || right.as_number().is_some_and(|n| n.is_f64());
+        match (left.as_f64(), right.as_f64()) {
+            (Some(a), Some(b)) if has_float => Ok(self.float_tolerance.approx_eq(a, b)),
+            _ => Ok(evaluate_binary(BinaryOp::Eq, left, right)? == Value::Bool(true)),
+        }
+    }
+
+    /// Evaluates a `match`: the result of the first arm whose value equals the
+    /// scrutinee, as with `==`, else the `_` arm. Only the chosen result is evaluated.
+    fn evaluate_match(&mut self, expr: &Expression) -> Result<Value, String> {
+        let Expression::Match { scrutinee, arms, default } = expr else {
+            unreachable!("evaluate_match called on {expr}")
+        };
+        let value = self.evaluate_expression(scrutinee)?;
+        for (pattern, result) in arms {
+            let pattern = self.evaluate_expression(pattern)?;
+            if self.values_equal(&value, &pattern)? {
+                return self.evaluate_expression(result);
+            }
+        }
+        match default {
+            Some(default) => self.evaluate_expression(default),
+            None => Err(format!("No match arm for {value}")),
+        }
+    }
+
+    /// Evaluates a chain of property accesses such as `a?.b.c`.
+    ///
+    /// A missing key reads as null. Reading a property of null is an error, unless
+    /// an optional `?.` is reached first, which makes the rest of the chain null.
+    fn evaluate_property_chain(&mut self, expr: &Expression) -> Result<Value, String> {
+        let mut links = Vec::new();
+        let mut base = expr;
+        while let Expression::Property { object, key, optional } = base {
+            links.push((key, *optional));
+            base = object;
+        }
+
+        let mut value = self.evaluate_expression(base)?;
+        for (key, optional) in links.into_iter().rev() {
+            value = match value {
+                Value::Null if optional => return Ok(Value::Null),
+                Value::Object(mut object) => object.remove(key).unwrap_or(Value::Null),
+                other => return Err(format!("Cannot read property '{key}' of {other}")),
+            };
+        }
+        Ok(value)
+    }
+
+    /// Evaluates a call to a builtin function with already evaluated arguments.
+    ///
+    /// Randomized builtins draw from the engine's generator, so they are
+    /// reproducible when the engine is seeded with [`GGLEngine::with_seed`].
+    fn evaluate_builtin_call(&mut self, name: &str, args: Vec<Value>) -> Result<Value, String> {
+        let expect_args = |count: usize| {
+            if args.len() == count {
+                Ok(())
+            } else {
+                Err(format!("{name} expects {count} argument(s), got {}", args.len()))
+            }
+        };
+        match name {
+            "shuffle" => {
+                expect_args(1)?;
+                let mut items = expect_array(name, &args[0])?.clone();
+                self.rng.shuffle(&mut items);
+                Ok(Value::Array(items))
+            }
+            "sample" => {
+                expect_args(2)?;
+                let mut items = expect_array(name, &args[0])?.clone();
+                let k = args[1].as_u64().ok_or_else(|| {
+                    format!("sample expects a non-negative integer count, got {}", args[1])
+                })? as usize;
+                if k > items.len() {
+                    return Err(format!(
+                        "sample size {k} exceeds array length {}",
+                        items.len()
+                    ));
+                }
+                // Partial Fisher–Yates: the first k slots end up a uniform sample
+                for i in 0..k {
+                    let j = self.rng.usize(i..items.len());
+                    items.swap(i, j);
+                }
+                items.truncate(k);
+                Ok(Value::Array(items))
+            }
+            "sum" | "product" => {
+                expect_args(1)?;
+                let (op, identity) = if name == "sum" {
+                    (BinaryOp::Add, 0)
+                } else {
+                    (BinaryOp::Mul, 1)
+                };
+                expect_numbers(name, &args[0])?
+                    .iter()
+                    .try_fold(Value::from(identity), |acc, item| {
+                        evaluate_binary(op, &acc, item)
+                    })
+            }
+            "average" => {
+                expect_args(1)?;
+                let items = expect_numbers(name, &args[0])?;
+                if items.is_empty() {
+                    return Err("average of an empty array".to_string());
+                }
+                let total: f64 = items.iter().filter_map(Value::as_f64).sum();
+                float_value(total / items.len() as f64)
+            }
+            "clamp" => {
+                expect_args(3)?;
+                let x = expect_number(name, &args[0])?;
+                let lo = expect_number(name, &args[1])?;
+                let hi = expect_number(name, &args[2])?;
+                if lo > hi {
+                    return Err(format!("clamp bounds are reversed: {lo} > {hi}"));
+                }
+                // Return the original value so integers stay integers
+                let index = if x < lo {
+                    1
+                } else if x > hi {
+                    2
+                } else {
+                    0
+                };
+                Ok(args[index].clone())
+            }
+            "lerp" => {
+                expect_args(3)?;
+                let a = expect_number(name, &args[0])?;
+                let b = expect_number(name, &args[1])?;
+                let t = expect_number(name, &args[2])?;
+                float_value(a + (b - a) * t)
+            }
+            "hypot" => {
+                // Euclidean norm of any number of components, e.g. hypot(dx, dy)
+                let squares = args
+                    .iter()
+                    .map(|arg| expect_number(name, arg).map(|x| x * x))
+                    .sum::<Result<f64, _>>()?;
+                float_value(squares.sqrt())
+            }
+            "proximity_edges" => {
+                expect_args(2)?;
+                let nodes = expect_array(name, &args[0])?;
+                let radius = expect_number(name, &args[1])?;
+                let positioned = nodes
+                    .iter()
+                    .map(|node| {
+                        let field = |key: &str| {
+                            node.get(key).cloned().ok_or_else(|| {
+                                format!("proximity_edges expects nodes with id, x and y, got {node}")
+                            })
+                        };
+                        Ok((
+                            field("id")?,
+                            expect_number(name, &field("x")?)?,
+                            expect_number(name, &field("y")?)?,
+                        ))
+                    })
+                    .collect::<Result<Vec<_>, String>>()?;
+
+                let mut edges = Vec::new();
+                for (i, (source, x1, y1)) in positioned.iter().enumerate() {
+                    for (target, x2, y2) in &positioned[i + 1..] {
+                        if (x2 - x1).hypot(y2 - y1) <= radius {
+                            edges.push(serde_json::json!({ "source": source, "target": target }));
+                        }
+                    }
+                }
+                Ok(Value::Array(edges))
+            }
+            "grid_edges" => {
+                if !(3..=4).contains(&args.len()) {
+                    return Err(format!(
+                        "grid_edges expects 3 or 4 arguments, got {}",
+                        args.len()
+                    ));
+                }
+                let dimension = |value: &Value| {
+                    value
+                        .as_u64()
+                        .map(|n| n as usize)
+                        .ok_or_else(|| {
+                            format!("grid_edges expects a non-negative integer size, got {value}")
+                        })
+                };
+                let (rows, cols) = (dimension(&args[0])?, dimension(&args[1])?);
+                let prefix = args[2]
+                    .as_str()
+                    .ok_or_else(|| format!("grid_edges expects a string prefix, got {}", args[2]))?;
+                let wrap = match args.get(3) {
+                    Some(value) => value.as_bool().ok_or_else(|| {
+                        format!("grid_edges expects a boolean wrap flag, got {value}")
+                    })?,
+                    None => false,
+                };
+
+                // Wrapping a row or column of one or two cells would only repeat a
+                // self-loop or an existing edge, so those wrap around no further
+                let wraps = |size: usize| wrap && size > 2;
+                let count = |size: usize, other: usize| {
+                    let links = if wraps(size) { size } else { size.saturating_sub(1) };
+                    links.saturating_mul(other)
+                };
+                let limits: HashMap<String, Value> =
+                    [("max_nodes", self.max_nodes), ("max_edges", self.max_edges)]
+                        .into_iter()
+                        .filter_map(|(key, limit)| Some((key.to_string(), Value::from(limit?))))
+                        .collect();
+                generators::check_size_limits(
+                    &limits,
+                    rows.saturating_mul(cols),
+                    count(cols, rows).saturating_add(count(rows, cols)),
+                )
+                .map_err(|e| format!("grid_edges error: {e}"))?;
+
+                // Same node names and edge order as the `grid` generator
+                let mut edges = Vec::new();
+                let mut connect = |(r1, c1): (usize, usize), (r2, c2): (usize, usize)| {
+                    edges.push(serde_json::json!({
+                        "source": format!("{prefix}{r1}_{c1}"),
+                        "target": format!("{prefix}{r2}_{c2}"),
+                    }));
+                };
+                for r in 0..rows {
+                    for c in 0..cols {
+                        if c + 1 < cols || wraps(cols) {
+                            connect((r, c), (r, (c + 1) % cols));
+                        }
+                        if r + 1 < rows || wraps(rows) {
+                            connect((r, c), ((r + 1) % rows, c));
+                        }
+                    }
+                }
+                Ok(Value::Array(edges))
+            }
+            "sortKeys" => {
+                expect_args(1)?;
+                if !args[0].is_object() {
+                    return Err(format!("sortKeys expects an object, got {}", args[0]));
+                }
+                Ok(sort_keys(&args[0]))
+            }
+            "get" => {
+                // get(object, key, default): the default stands in for missing and null values
+                expect_args(3)?;
+                let key = args[1]
+                    .as_str()
+                    .ok_or_else(|| format!("get expects a string key, got {}", args[1]))?;
+                let value = match &args[0] {
+                    Value::Object(object) => object.get(key),
+                    Value::Null => None,
+                    other => return Err(format!("get expects an object, got {other}")),
+                };
+                Ok(value.filter(|v| !v.is_null()).unwrap_or(&args[2]).clone())
+            }
+            "assert" => {
+                // Conditions must be booleans, as in `while`
+                expect_args(2)?;
+                let message = match &args[1] {
+                    Value::String(text) => text.clone(),
+                    other => other.to_string(),
+                };
+                match args[0] {
+                    Value::Bool(true) => Ok(Value::Bool(true)),
+                    Value::Bool(false) => Err(format!("Assertion failed: {message}")),
+                    ref other => Err(format!("assert expects a boolean condition, got {other}")),
+                }
+            }
+            "len" => {
+                // Elements of an array, characters of a string, keys of an object
+                expect_args(1)?;
+                let count = match &args[0] {
+                    Value::Array(items) => items.len(),
+                    Value::String(text) => text.chars().count(),
+                    Value::Object(object) => object.len(),
+                    other => {
+                        return Err(format!(
+                            "len expects an array, string or object, got {other}"
+                        ))
+                    }
+                };
+                Ok(Value::from(count))
+            }
+            "fromEntries" => {
+                // Builds an object from [key, value] pairs; later duplicates win
+                expect_args(1)?;
+                let mut object = serde_json::Map::new();
+                for pair in expect_array(name, &args[0])? {
+                    let (key, value) = match pair.as_array().map(Vec::as_slice) {
+                        Some([key, value]) => (key, value),
+                        _ => {
+                            return Err(format!(
+                                "fromEntries expects [key, value] pairs, got {pair}"
+                            ))
+                        }
+                    };
+                    let key = match key {
+                        Value::String(text) => text.clone(),
+                        other => other.to_string(),
+                    };
+                    object.insert(key, value.clone());
+                }
+                Ok(Value::Object(object))
+            }
+            "merge" => {
+                // Shallow merge of any number of objects; later keys win
+                let mut merged = serde_json::Map::new();
+                for arg in &args {
+                    let object = arg
+                        .as_object()
+                        .ok_or_else(|| format!("merge expects objects, got {arg}"))?;
+                    merged.extend(object.clone());
+                }
+                Ok(Value::Object(merged))
+            }
+            _ => Err(format!("Unknown function: {name}")),
+        }
+    }
+}
+
+/// Fails if `graph` holds more nodes or edges than the given limits allow.
+fn check_graph_limits(
+    graph: &Graph,
+    max_nodes: Option<usize>,
+    max_edges: Option<usize>,
+) -> Result<(), String> {
+    if let Some(max) = max_nodes {
+        if graph.nodes.len() > max {
+            return Err(format!("Graph exceeds the limit of {max} nodes"));
+        }
+    }
+    if let Some(max) = max_edges {
+        if graph.edges.len() > max {
+            return Err(format!("Graph exceeds the limit of {max} edges"));
+        }
+    }
+    Ok(())
+}
+
+fn expect_array<'a>(function: &str, value: &'a Value) -> Result<&'a Vec<Value>, String> {
+    value
+        .as_array()
+        .ok_or_else(|| format!("{function} expects an array, got {value}"))
+}
+
+/// Rounds every float in `value`, including nested ones, to `digits` significant digits.
+fn round_floats(value: &mut Value, digits: usize) {
+    match value {
+        Value::Number(number) if number.is_f64() => {
+            let rounded = number
+                .as_f64()
+                .and_then(|f| format!("{f:.*e}", digits.saturating_sub(1)).parse().ok())
+                .and_then(serde_json::Number::from_f64);
+            if let Some(rounded) = rounded {
+                *number = rounded;
+            }
         }
+        Value::Array(items) => items.iter_mut().for_each(|item| round_floats(item, digits)),
+        Value::Object(object) => object
+            .values_mut()
+            .for_each(|item| round_floats(item, digits)),
+        _ => {}
     }
 }
+
+/// Rebuilds objects, including nested ones, with their keys in lexicographic order.
+fn sort_keys(value: &Value) -> Value {
+    match value {
+        Value::Object(object) => {
+            let mut entries: Vec<_> = object.iter().collect();
+            entries.sort_by_key(|(key, _)| key.as_str());
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key.clone(), sort_keys(value)))
+                    .collect(),
+            )
+        }
+        Value::Array(items) => Value::Array(items.iter().map(sort_keys).collect()),
+        other => other.clone(),
+    }
+}
+
+/// Summarizes a value for the call trace: collections by size, scalars as-is.
+fn describe_value(value: &Value) -> String {
+    match value {
+        Value::Array(items) => format!("[{} items]", items.len()),
+        Value::Object(object) => format!("{{{} keys}}", object.len()),
+        other => other.to_string(),
+    }
+}
+
+fn expect_number(function: &str, value: &Value) -> Result<f64, String> {
+    value
+        .as_f64()
+        .ok_or_else(|| format!("{function} expects a number, got {value}"))
+}
+
+fn expect_numbers<'a>(function: &str, value: &'a Value) -> Result<&'a Vec<Value>, String> {
+    let items = expect_array(function, value)?;
+    match items.iter().find(|item| !item.is_number()) {
+        Some(item) => Err(format!("{function} expects numeric elements, got {item}")),
+        None => Ok(items),
+    }
+}
+
+/// Orders numbers numerically, strings lexicographically and arrays element by
+/// element, with a shorter prefix first. Returns `None` for any other pair, in
+/// which case `==` falls back to structural equality, e.g. for objects.
+fn compare_values(left: &Value, right: &Value) -> Option<Ordering> {
+    match (left, right) {
+        (Value::Number(a), Value::Number(b)) => compare_numbers(a, b),
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        (Value::Array(a), Value::Array(b)) => {
+            for (x, y) in a.iter().zip(b) {
+                match compare_values(x, y)? {
+                    Ordering::Equal => {}
+                    ordering => return Some(ordering),
+                }
+            }
+            Some(a.len().cmp(&b.len()))
+        }
+        _ => None,
+    }
+}
+
+/// Compares two integers exactly, since integers above 2^53 lose precision as
+/// `f64`. Only comparisons involving a float go through `f64`.
+fn compare_numbers(a: &serde_json::Number, b: &serde_json::Number) -> Option<Ordering> {
+    if a.is_f64() || b.is_f64() {
+        return a.as_f64()?.partial_cmp(&b.as_f64()?);
+    }
+    // An integer that does not fit in i64 is a u64 above i64::MAX
+    Some(match (a.as_i64(), b.as_i64()) {
+        (Some(x), Some(y)) => x.cmp(&y),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => a.as_u64()?.cmp(&b.as_u64()?),
+    })
+}
+
+/// Applies an arithmetic or comparison operator to two evaluated operands.
+///
+/// Integer operands stay integers, except for `/` when the division is not exact.
+/// `+` with a string on either side concatenates. Numbers compare by value (so
+/// `1 == 1.0`), strings lexicographically; `==`/`!=` work on any values. `??`
+/// picks `right` when `left` is null.
+pub(crate) fn evaluate_binary(op: BinaryOp, left: &Value, right: &Value) -> Result<Value, String> {
+    let type_error = || format!("Cannot apply '{op}' to {left} and {right}");
+
+    let ordering = compare_values(left, right);
+    match op {
+        BinaryOp::Coalesce => return Ok(if left.is_null() { right } else { left }.clone()),
+        BinaryOp::Eq => return Ok(Value::Bool(ordering.map_or(left == right, Ordering::is_eq))),
+        BinaryOp::Ne => return Ok(Value::Bool(!ordering.map_or(left == right, Ordering::is_eq))),
+        BinaryOp::Lt | BinaryOp::Le | BinaryOp::Gt | BinaryOp::Ge => {
+            if left.is_boolean() || right.is_boolean() {
+                return Err(format!(
+                    "Booleans only support '==' and '!=', got {left} {op} {right}"
+                ));
+            }
+            let ordering = ordering.ok_or_else(type_error)?;
+            let result = match op {
+                BinaryOp::Lt => ordering.is_lt(),
+                BinaryOp::Le => ordering.is_le(),
+                BinaryOp::Gt => ordering.is_gt(),
+                _ => ordering.is_ge(),
+            };
+            return Ok(Value::Bool(result));
+        }
+        _ => {}
+    }
+
+    if op == BinaryOp::Add && (left.is_string() || right.is_string()) {
+        let text = |v: &Value| v.as_str().map(str::to_string).unwrap_or_else(|| v.to_string());
+        return Ok(Value::String(text(left) + &text(right)));
+    }
+
+    if let (Some(a), Some(b)) = (left.as_i64(), right.as_i64()) {
+        let result = match op {
+            BinaryOp::Add => a.checked_add(b),
+            BinaryOp::Sub => a.checked_sub(b),
+            BinaryOp::Mul => a.checked_mul(b),
+            BinaryOp::Div | BinaryOp::Mod if b == 0 => return Err("Division by zero".to_string()),
+            BinaryOp::Div if a % b != 0 => return float_value(a as f64 / b as f64),
+            BinaryOp::Div => a.checked_div(b),
+            _ => a.checked_rem(b),
+        };
+        return result
+            .map(Value::from)
+            .ok_or_else(|| format!("Integer overflow in {a} {op} {b}"));
+    }
+
+    let a = left.as_f64().ok_or_else(type_error)?;
+    let b = right.as_f64().ok_or_else(type_error)?;
+    match op {
+        BinaryOp::Add => float_value(a + b),
+        BinaryOp::Sub => float_value(a - b),
+        BinaryOp::Mul => float_value(a * b),
+        BinaryOp::Div | BinaryOp::Mod if b == 0.0 => Err("Division by zero".to_string()),
+        BinaryOp::Div => float_value(a / b),
+        _ => float_value(a % b),
+    }
+}
+
+/// Applies a sign to an evaluated operand. Both signs require a number, and
+/// negation keeps integers integral.
+pub(crate) fn evaluate_unary(op: UnaryOp, value: &Value) -> Result<Value, String> {
+    if let Some(i) = value.as_i64() {
+        return match op {
+            UnaryOp::Neg => i
+                .checked_neg()
+                .map(Value::from)
+                .ok_or_else(|| format!("Integer overflow in -{i}")),
+            UnaryOp::Plus => Ok(value.clone()),
+        };
+    }
+    let x = value
+        .as_f64()
+        .ok_or_else(|| format!("Cannot apply unary '{op}' to {value}"))?;
+    match op {
+        UnaryOp::Neg => float_value(-x),
+        UnaryOp::Plus => Ok(value.clone()),
+    }
+}
+
+fn float_value(f: f64) -> Result<Value, String> {
+    serde_json::Number::from_f64(f)
+        .map(Value::Number)
+        .ok_or_else(|| format!("Non-finite number produced: {f}"))
+}
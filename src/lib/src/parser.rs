@@ -1,7 +1,7 @@
 //! GGL language parser and Abstract Syntax Tree (AST) definitions.
 //! This module uses the `pest` library to parse GGL source code into a structured AST.
 
-use pest::iterators::Pair;
+use pest::iterators::{Pair, Pairs};
 use pest::Parser as PestParser;
 use pest_derive::Parser;
 use std::fmt;
@@ -9,6 +9,17 @@ use std::fmt;
 /// Type alias for boxed pest error to reduce Result size
 type ParseError = Box<pest::error::Error<Rule>>;
 
+/// Maximum depth of nested brackets, braces, parentheses and string interpolations
+/// accepted by [`parse_ggl`]. Operators and `.key` accessors count as a level each,
+/// because a chain of them nests as deeply as it is long.
+///
+/// The parser, evaluator, and serializer are all recursive, so unbounded nesting
+/// would overflow the stack instead of producing an error. The limit keeps about
+/// 1.4x headroom on a 1 MiB stack (the wasm default) even in unoptimized builds,
+/// where nested string interpolations, the costliest kind, overflow after ~140
+/// levels; optimized builds manage several hundred.
+pub const MAX_NESTING_DEPTH: usize = 100;
+
 #[derive(Parser)]
 #[grammar = "ggl.pest"]
 pub struct GglParser;
@@ -17,6 +28,7 @@ pub struct GglParser;
 
 #[derive(Debug)]
 pub struct GraphAST {
+    /// Empty for an anonymous `graph { ... }`.
     pub name: String,
     pub statements: Vec<Statement>,
 }
@@ -25,6 +37,7 @@ pub struct GraphAST {
 pub enum Statement {
     Let(LetStatement),
     For(ForStatement),
+    While(WhileStatement),
     Node(NodeDeclaration),
     Edge(EdgeDeclaration),
     Generate(GenerateStatement),
@@ -54,20 +67,49 @@ pub struct LetStatement {
 /// for i in 0..node_count {
 ///     node "node_{i}";
 /// }
+///
+/// for name in ["web", "db"] {
+///     node "{name}";
+/// }
 /// ```
 #[derive(Debug, Clone)]
 pub struct ForStatement {
     pub variable: String,
-    pub start: Expression,
-    pub end: Expression,
+    pub iterable: ForIterable,
     pub body: Vec<Statement>,
 }
 
+/// Represents a `while` loop, repeated as long as its condition evaluates to `true`.
+///
+/// # Example
+///
+/// ```ggl
+/// while node_count < 10 {
+///     node "n{node_count}";
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct WhileStatement {
+    pub condition: Expression,
+    pub body: Vec<Statement>,
+}
+
+/// What a `for` loop iterates over.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ForIterable {
+    /// The integers `start..end`, end exclusive.
+    Range { start: Expression, end: Expression },
+    /// The elements of an expression evaluating to an array.
+    Items(Expression),
+}
+
 #[derive(Debug, Clone)]
 pub struct NodeDeclaration {
     pub id: Expression,
     pub node_type: Option<Expression>,
     pub attributes: Vec<(String, Expression)>,
+    /// Text of the `#` doc comment lines preceding the declaration.
+    pub doc: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -77,6 +119,8 @@ pub struct EdgeDeclaration {
     pub target: Expression,
     pub directed: bool,
     pub attributes: Vec<(String, Expression)>,
+    /// Text of the `#` doc comment lines preceding the declaration.
+    pub doc: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -111,7 +155,94 @@ pub enum Expression {
     Integer(i64),
     Float(f64),
     Boolean(bool),
+    Null,
     Identifier(String),
+    /// A rule attribute variable, e.g. `$C` in `node N [count=$C]`.
+    Variable(String),
+    Array(Vec<Expression>),
+    Object(Vec<(String, Expression)>),
+    /// A builtin function call, e.g. `shuffle(items)`.
+    Call { name: String, args: Vec<Expression> },
+    /// `...items` in a call's arguments, passing each element as an argument.
+    Spread(Box<Expression>),
+    /// `match (scrutinee) { value => result, _ => default }`; arms are tried in order.
+    Match {
+        scrutinee: Box<Expression>,
+        arms: Vec<(Expression, Expression)>,
+        default: Option<Box<Expression>>,
+    },
+    /// Object property access: `object.key`, or `object?.key` when `optional`.
+    Property {
+        object: Box<Expression>,
+        key: String,
+        optional: bool,
+    },
+    /// The `*` in a `[key=*]` rule pattern, matching any value.
+    Wildcard,
+    /// A sign applied to one operand, e.g. `-x`.
+    Unary { op: UnaryOp, operand: Box<Expression> },
+    /// Arithmetic on two operands, e.g. `scale * 2`.
+    Binary {
+        op: BinaryOp,
+        left: Box<Expression>,
+        right: Box<Expression>,
+    },
+}
+
+/// Prefix operators usable in expressions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOp {
+    /// `-x`
+    Neg,
+    /// `+x`, which only checks that `x` is a number.
+    Plus,
+}
+
+impl fmt::Display for UnaryOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnaryOp::Neg => write!(f, "-"),
+            UnaryOp::Plus => write!(f, "+"),
+        }
+    }
+}
+
+/// Arithmetic and comparison operators usable in expressions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    /// `??`: the right operand, evaluated only when the left one is null.
+    Coalesce,
+}
+
+impl fmt::Display for BinaryOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbol = match self {
+            BinaryOp::Add => "+",
+            BinaryOp::Sub => "-",
+            BinaryOp::Mul => "*",
+            BinaryOp::Div => "/",
+            BinaryOp::Mod => "%",
+            BinaryOp::Eq => "==",
+            BinaryOp::Ne => "!=",
+            BinaryOp::Lt => "<",
+            BinaryOp::Le => "<=",
+            BinaryOp::Gt => ">",
+            BinaryOp::Ge => ">=",
+            BinaryOp::Coalesce => "??",
+        };
+        write!(f, "{symbol}")
+    }
 }
 
 /// Implements the Display trait to allow Expressions to be converted to strings.
@@ -123,7 +254,61 @@ impl fmt::Display for Expression {
             Expression::Integer(i) => write!(f, "{i}"),
             Expression::Float(n) => write!(f, "{n}"),
             Expression::Boolean(b) => write!(f, "{b}"),
+            Expression::Null => write!(f, "null"),
             Expression::Identifier(name) => write!(f, "{name}"),
+            Expression::Variable(name) => write!(f, "${name}"),
+            Expression::Array(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                write!(f, "]")
+            }
+            Expression::Object(pairs) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in pairs.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{key}: {value}")?;
+                }
+                write!(f, "}}")
+            }
+            Expression::Unary { op, operand } => write!(f, "{op}{operand}"),
+            Expression::Binary { op, left, right } => write!(f, "({left} {op} {right})"),
+            Expression::Wildcard => write!(f, "*"),
+            Expression::Spread(items) => write!(f, "...{items}"),
+            Expression::Call { name, args } => {
+                write!(f, "{name}(")?;
+                for (i, arg) in args.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{arg}")?;
+                }
+                write!(f, ")")
+            }
+            Expression::Property { object, key, optional } => {
+                let dot = if *optional { "?." } else { "." };
+                write!(f, "{object}{dot}{key}")
+            }
+            Expression::Match { scrutinee, arms, default } => {
+                write!(f, "match ({scrutinee}) {{")?;
+                for (i, (value, result)) in arms.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, " {value} => {result}")?;
+                }
+                if let Some(default) = default {
+                    let separator = if arms.is_empty() { "" } else { "," };
+                    write!(f, "{separator} _ => {default}")?;
+                }
+                write!(f, " }}")
+            }
             Expression::FormattedString(parts) => {
                 // This formatting is for pattern matching in rules, where variables
                 // are not yet resolved.
@@ -131,6 +316,7 @@ impl fmt::Display for Expression {
                     match part {
                         StringPart::Literal(s) => write!(f, "{s}")?,
                         StringPart::Variable(v) => write!(f, "{{{v}}}")?,
+                        StringPart::Expression(e) => write!(f, "{{{e}}}")?,
                     }
                 }
                 Ok(())
@@ -143,16 +329,186 @@ impl fmt::Display for Expression {
 pub enum StringPart {
     Literal(String),
     Variable(String),
+    /// Any other interpolated expression, e.g. `{i + 1}`.
+    Expression(Expression),
 }
 
 // --- Parser Implementation ---
 
 /// Parses a GGL source string into a Graph AST.
 pub fn parse_ggl(source: &str) -> Result<GraphAST, ParseError> {
+    check_nesting_depth(source)?;
     let file_pair = GglParser::parse(Rule::file, source).map_err(Box::new)?.next().unwrap();
     build_ast_from_file(file_pair)
 }
 
+/// Returns the byte offset of the first `//` or `/* */` comment in `source`, or
+/// `None` if it has none or does not parse.
+///
+/// Comments are skipped implicitly, so they can only sit in the gaps between the
+/// parsed tokens. The text of a leaf pair such as a string is its content and is
+/// never mistaken for one.
+pub(crate) fn find_comment(source: &str) -> Option<usize> {
+    let pairs = GglParser::parse(Rule::file, source).ok()?;
+    let mut gap_start = 0;
+    let mut opened_leaf = None;
+    for token in pairs.tokens() {
+        let (position, in_leaf) = match token {
+            pest::Token::Start { rule, pos } => {
+                opened_leaf = Some(rule);
+                (pos.pos(), false)
+            }
+            pest::Token::End { rule, pos } => (pos.pos(), opened_leaf.take() == Some(rule)),
+        };
+        let gap = &source[gap_start..position];
+        let comment = [gap.find("//"), gap.find("/*")].into_iter().flatten().min();
+        if let (false, Some(offset)) = (in_leaf, comment) {
+            return Some(gap_start + offset);
+        }
+        gap_start = position;
+    }
+    None
+}
+
+/// Rejects sources whose nesting exceeds [`MAX_NESTING_DEPTH`].
+///
+/// Brackets, braces, parentheses and `{...}` interpolations in formatted strings
+/// each add a level. So does every operator or `.key` accessor, since a chain of
+/// them builds an expression tree as deep as it is long; the count resets at each
+/// `,` and `;` and after a `}` that the expression does not continue past, such as
+/// the end of a block. The scan follows the grammar's lexical structure: brackets
+/// and operators in strings and comments do not count, and strings inside
+/// interpolations are scanned as nested strings.
+fn check_nesting_depth(source: &str) -> Result<(), ParseError> {
+    /// What the scanner is inside of, innermost last.
+    #[derive(PartialEq)]
+    enum Frame {
+        Bracket,
+        String,
+        Interpolation,
+    }
+    let is_operator = |byte: u8| b"+-*/%<>=!?".contains(&byte);
+    let bytes = source.as_bytes();
+    // Each frame with the number of operators counted in it since its last separator
+    let mut frames: Vec<(Frame, usize)> = Vec::new();
+    let mut depth = 0usize;
+    // Set by a `}`: the next byte tells whether an expression continues after it
+    let mut after_brace = false;
+    let mut i = 0;
+    while i < bytes.len() {
+        let byte = bytes[i];
+        let (mut opens_level, mut extends_chain) = (false, false);
+        if matches!(frames.last(), Some((Frame::String, _))) {
+            match byte {
+                b'"' => {
+                    frames.pop();
+                }
+                b'{' => {
+                    frames.push((Frame::Interpolation, 0));
+                    opens_level = true;
+                }
+                _ => {}
+            }
+        } else {
+            match byte {
+                b'"' => frames.push((Frame::String, 0)),
+                b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                    while i < bytes.len() && bytes[i] != b'\n' {
+                        i += 1;
+                    }
+                }
+                b'#' => {
+                    while i < bytes.len() && bytes[i] != b'\n' {
+                        i += 1;
+                    }
+                }
+                b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                    i += 2;
+                    while i < bytes.len() && !bytes[i..].starts_with(b"*/") {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+                _ if after_brace && !byte.is_ascii_whitespace() => {
+                    // Unless an operator, accessor or index continues the expression,
+                    // the `}` ended a block and a new statement follows
+                    if !b".[(".contains(&byte) && !is_operator(byte) {
+                        if let Some((_, operators)) = frames.last_mut() {
+                            depth -= *operators;
+                            *operators = 0;
+                        }
+                    }
+                    after_brace = false;
+                    continue;
+                }
+                b'[' | b'{' | b'(' => {
+                    frames.push((Frame::Bracket, 0));
+                    opens_level = true;
+                }
+                b']' | b'}' | b')' => {
+                    // Closes a bracket, or an interpolation and returns to its string
+                    if let Some((_, operators)) = frames.pop() {
+                        depth -= 1 + operators;
+                    }
+                    after_brace = byte == b'}' && !matches!(frames.last(), Some((Frame::String, _)));
+                }
+                b',' | b';' => {
+                    if let Some((_, operators)) = frames.last_mut() {
+                        depth -= *operators;
+                        *operators = 0;
+                    }
+                }
+                // A range or spread, or an accessor; a `.` followed by a digit is in
+                // a float
+                b'.' if bytes.get(i + 1) == Some(&b'.') => {
+                    while bytes.get(i + 1) == Some(&b'.') {
+                        i += 1;
+                    }
+                }
+                b'.' => {
+                    extends_chain = bytes
+                        .get(i + 1)
+                        .is_some_and(|b| b.is_ascii_alphabetic() || *b == b'_');
+                }
+                _ if is_operator(byte) => {
+                    let start = i;
+                    while bytes.get(i + 1).is_some_and(|&b| is_operator(b))
+                        && !(bytes[i + 1] == b'/' && matches!(bytes.get(i + 2), Some(b'/' | b'*')))
+                    {
+                        i += 1;
+                    }
+                    // `?.` is a single accessor, and a lone `=` only assigns
+                    if bytes[i] == b'?' && bytes.get(i + 1) == Some(&b'.') {
+                        i += 1;
+                    }
+                    extends_chain = &bytes[start..=i] != b"=";
+                }
+                _ => {}
+            }
+        }
+        if extends_chain {
+            if let Some((_, operators)) = frames.last_mut() {
+                *operators += 1;
+                opens_level = true;
+            }
+        }
+        if opens_level {
+            depth += 1;
+            if depth > MAX_NESTING_DEPTH {
+                let position = pest::Position::new(source, i).unwrap();
+                return Err(Box::new(pest::error::Error::new_from_pos(
+                    pest::error::ErrorVariant::CustomError {
+                        message: format!("Nesting exceeds the maximum depth of {MAX_NESTING_DEPTH}"),
+                    },
+                    position,
+                )));
+            }
+        }
+        i += 1;
+    }
+    Ok(())
+}
+
 fn build_ast_from_file(pair: Pair<Rule>) -> Result<GraphAST, ParseError> {
     let mut inner = pair.into_inner();
 
@@ -168,11 +524,10 @@ fn build_ast_from_file(pair: Pair<Rule>) -> Result<GraphAST, ParseError> {
         (name, statements)
     } else if first.as_rule() == Rule::EOI {
         // Empty graph with no name
-        let name = "unnamed".to_string();
-        (name, vec![])
+        (String::new(), vec![])
     } else {
         // Graph has no name, first item is a statement
-        let name = "unnamed".to_string();
+        let name = String::new();
         let mut statements = vec![build_statement(first)?];
         statements.extend(
             inner
@@ -190,6 +545,7 @@ fn build_statement(pair: Pair<Rule>) -> Result<Statement, ParseError> {
     match pair.as_rule() {
         Rule::let_declaration => build_let_statement(pair).map(Statement::Let),
         Rule::for_loop => build_for_loop(pair).map(Statement::For),
+        Rule::while_loop => build_while_loop(pair).map(Statement::While),
         Rule::node_declaration => build_node_declaration(pair).map(Statement::Node),
         Rule::edge_declaration => build_edge_declaration(pair).map(Statement::Edge),
         Rule::generate_statement => build_generate_statement(pair).map(Statement::Generate),
@@ -199,6 +555,16 @@ fn build_statement(pair: Pair<Rule>) -> Result<Statement, ParseError> {
     }
 }
 
+/// Builds the statements of a loop body. A plain loop rather than `collect` keeps
+/// the stack cost of nested blocks low.
+fn build_block(pairs: Pairs<Rule>) -> Result<Vec<Statement>, ParseError> {
+    let mut statements = Vec::new();
+    for pair in pairs {
+        statements.push(build_statement(pair)?);
+    }
+    Ok(statements)
+}
+
 fn build_let_statement(pair: Pair<Rule>) -> Result<LetStatement, ParseError> {
     let mut inner = pair.into_inner();
     let name = inner.next().unwrap().as_str().to_string();
@@ -206,22 +572,55 @@ fn build_let_statement(pair: Pair<Rule>) -> Result<LetStatement, ParseError> {
     Ok(LetStatement { name, value })
 }
 
+fn build_while_loop(pair: Pair<Rule>) -> Result<WhileStatement, ParseError> {
+    let mut inner = pair.into_inner();
+    let condition = build_expression(inner.next().unwrap())?;
+    let body = build_block(inner)?;
+    Ok(WhileStatement { condition, body })
+}
+
 fn build_for_loop(pair: Pair<Rule>) -> Result<ForStatement, ParseError> {
     let mut inner = pair.into_inner();
     let variable = inner.next().unwrap().as_str().to_string();
-    let start = build_expression(inner.next().unwrap())?;
-    let end = build_expression(inner.next().unwrap())?;
-    let body = inner.map(build_statement).collect::<Result<_, _>>()?;
+    let iterable_pair = inner.next().unwrap();
+    let iterable = match iterable_pair.as_rule() {
+        Rule::range => {
+            let mut bounds = iterable_pair.into_inner();
+            ForIterable::Range {
+                start: build_expression(bounds.next().unwrap())?,
+                end: build_expression(bounds.next().unwrap())?,
+            }
+        }
+        _ => ForIterable::Items(build_expression(iterable_pair)?),
+    };
+    let body = build_block(inner)?;
     Ok(ForStatement {
         variable,
-        start,
-        end,
+        iterable,
         body,
     })
 }
 
+/// Joins leading `#` doc comment lines, dropping the `#` and one following space.
+fn build_doc(pairs: &mut Vec<Pair<Rule>>) -> Option<String> {
+    let count = pairs.iter().take_while(|p| p.as_rule() == Rule::doc_comment).count();
+    if count == 0 {
+        return None;
+    }
+    let lines: Vec<String> = pairs
+        .drain(..count)
+        .map(|p| {
+            let text = &p.as_str()[1..];
+            text.strip_prefix(' ').unwrap_or(text).trim_end().to_string()
+        })
+        .collect();
+    Some(lines.join("\n"))
+}
+
 fn build_node_declaration(pair: Pair<Rule>) -> Result<NodeDeclaration, ParseError> {
-    let mut inner = pair.into_inner();
+    let mut pairs: Vec<_> = pair.into_inner().collect();
+    let doc = build_doc(&mut pairs);
+    let mut inner = pairs.into_iter();
     let id = build_expression(inner.next().unwrap())?;
     let next = inner.next();
     let (node_type, attributes) = match next {
@@ -233,12 +632,13 @@ fn build_node_declaration(pair: Pair<Rule>) -> Result<NodeDeclaration, ParseErro
         Some(pair) if pair.as_rule() == Rule::attributes => (None, build_attributes(pair)?),
         _ => (None, vec![]),
     };
-    Ok(NodeDeclaration { id, node_type, attributes })
+    Ok(NodeDeclaration { id, node_type, attributes, doc })
 }
 
 fn build_edge_declaration(pair: Pair<Rule>) -> Result<EdgeDeclaration, ParseError> {
     let span = pair.as_span(); // Capture span before moving pair
     let mut inner_pairs: Vec<_> = pair.into_inner().collect();
+    let doc = build_doc(&mut inner_pairs);
 
     let operator_pos = inner_pairs.iter().position(|p| p.as_rule() == Rule::edge_operator)
         .ok_or_else(|| Box::new(pest::error::Error::new_from_span(
@@ -282,7 +682,7 @@ fn build_edge_declaration(pair: Pair<Rule>) -> Result<EdgeDeclaration, ParseErro
         target = build_expression(inner_pairs[2].clone())?; // Skip operator
     }
 
-    Ok(EdgeDeclaration { id, source, target, directed, attributes })
+    Ok(EdgeDeclaration { id, source, target, directed, attributes, doc })
 }
 
 
@@ -337,43 +737,213 @@ fn build_attributes(pair: Pair<Rule>) -> Result<Vec<(String, Expression)>, Parse
         .map(|p| -> Result<(String, Expression), ParseError> {
             let mut kv = p.into_inner();
             let key = kv.next().unwrap().as_str().to_string();
-            let value = build_expression(kv.next().unwrap())?;
+            let value = kv.next().unwrap();
+            let value = if value.as_rule() == Rule::wildcard {
+                Expression::Wildcard
+            } else {
+                build_expression(value)?
+            };
             Ok((key, value))
         })
         .collect()
 }
 
-fn build_expression(pair: Pair<Rule>) -> Result<Expression, ParseError> {
+// `build_expression` recurses once per nesting level, so it only dispatches; the
+// helpers it calls hold the locals.
+fn build_expression(mut pair: Pair<Rule>) -> Result<Expression, ParseError> {
+    // Descend through operator rules with a single operand without recursing
+    while matches!(pair.as_rule(), Rule::expression | Rule::sum | Rule::term) {
+        let mut inner = pair.clone().into_inner();
+        let first = inner.next().unwrap();
+        if inner.next().is_some() {
+            return build_operators(pair);
+        }
+        pair = first;
+    }
     match pair.as_rule() {
-        Rule::expression => {
-            let inner = pair.into_inner().next().unwrap();
-            build_expression(inner)
-        },
         Rule::literal => build_literal(pair),
+        Rule::string | Rule::integer | Rule::float | Rule::boolean | Rule::null => build_scalar(pair),
+        Rule::unary => build_unary(pair),
         Rule::identifier => Ok(Expression::Identifier(pair.as_str().to_string())),
-        Rule::formatted_string => {
-            let parts = pair.into_inner().map(|p| match p.as_rule() {
-                Rule::string_part => StringPart::Literal(p.as_str().to_string()),
-                Rule::var_in_string => StringPart::Variable(p.into_inner().next().unwrap().as_str().to_string()),
-                _ => unreachable!(),
-            }).collect();
-            Ok(Expression::FormattedString(parts))
-        },
-        Rule::string => {
-            // Direct string literal
-            let content = pair.as_str();
-            let trimmed = &content[1..content.len()-1]; // Remove quotes
-            Ok(Expression::StringLiteral(trimmed.to_string()))
-        },
-        Rule::integer => Ok(Expression::Integer(pair.as_str().parse().unwrap())),
-        Rule::float => Ok(Expression::Float(pair.as_str().parse().unwrap())),
-        Rule::boolean => Ok(Expression::Boolean(pair.as_str().parse().unwrap())),
+        Rule::variable => Ok(Expression::Variable(pair.as_str()[1..].to_string())),
+        Rule::formatted_string => build_formatted_string(pair),
+        Rule::array => build_list(pair).map(Expression::Array),
+        Rule::call => build_call(pair),
+        Rule::match_expression => build_match(pair),
+        Rule::object => build_object(pair),
         _ => unreachable!("Unexpected expression rule: {:?}", pair.as_rule()),
     }
 }
 
+/// Builds an operator chain such as `a + b * c` or `a ?? b.c`.
+fn build_operators(pair: Pair<Rule>) -> Result<Expression, ParseError> {
+    let mut inner = pair.into_inner();
+    let mut expr = build_expression(inner.next().unwrap())?;
+    let mut last_op = None;
+    while let Some(op_pair) = inner.next() {
+        if op_pair.as_rule() == Rule::accessor {
+            expr = push_accessor(expr, op_pair, last_op.is_some());
+            continue;
+        }
+        let op = binary_op(op_pair.as_str());
+        let right = build_expression(inner.next().unwrap())?;
+        expr = push_operator(expr, op, right, last_op);
+        last_op = Some(op);
+    }
+    Ok(expr)
+}
+
+/// Builds every inner pair as an expression, e.g. array items.
+fn build_list(pair: Pair<Rule>) -> Result<Vec<Expression>, ParseError> {
+    let mut items = Vec::new();
+    for item in pair.into_inner() {
+        items.push(build_expression(item)?);
+    }
+    Ok(items)
+}
+
+fn build_call(pair: Pair<Rule>) -> Result<Expression, ParseError> {
+    let mut inner = pair.into_inner();
+    let name = inner.next().unwrap().as_str().to_string();
+    let mut args = Vec::new();
+    for arg in inner {
+        args.push(match arg.as_rule() {
+            Rule::spread => {
+                Expression::Spread(Box::new(build_expression(arg.into_inner().next().unwrap())?))
+            }
+            _ => build_expression(arg)?,
+        });
+    }
+    Ok(Expression::Call { name, args })
+}
+
+fn build_match(pair: Pair<Rule>) -> Result<Expression, ParseError> {
+    let mut inner = pair.into_inner();
+    let scrutinee = Box::new(build_expression(inner.next().unwrap())?);
+    let mut arms = Vec::new();
+    let mut default = None;
+    for arm in inner {
+        let rule = arm.as_rule();
+        let mut parts = arm.into_inner();
+        if rule == Rule::match_default {
+            default = Some(Box::new(build_expression(parts.next().unwrap())?));
+        } else {
+            let value = build_expression(parts.next().unwrap())?;
+            arms.push((value, build_expression(parts.next().unwrap())?));
+        }
+    }
+    Ok(Expression::Match { scrutinee, arms, default })
+}
+
+fn build_object(pair: Pair<Rule>) -> Result<Expression, ParseError> {
+    let mut pairs = Vec::new();
+    for object_pair in pair.into_inner() {
+        let mut kv = object_pair.into_inner();
+        let key_pair = kv.next().unwrap();
+        let key = match key_pair.as_rule() {
+            Rule::string => {
+                let content = key_pair.as_str();
+                content[1..content.len() - 1].to_string() // Remove quotes
+            }
+            _ => key_pair.as_str().to_string(),
+        };
+        let value = match kv.next() {
+            Some(value) => build_expression(value)?,
+            None => Expression::Identifier(key.clone()), // Shorthand `{key}`
+        };
+        pairs.push((key, value));
+    }
+    Ok(Expression::Object(pairs))
+}
+
+fn build_formatted_string(pair: Pair<Rule>) -> Result<Expression, ParseError> {
+    let mut parts = Vec::new();
+    for part in pair.into_inner() {
+        parts.push(match part.as_rule() {
+            Rule::string_part => StringPart::Literal(part.as_str().to_string()),
+            Rule::var_in_string => match build_expression(part.into_inner().next().unwrap())? {
+                Expression::Identifier(name) => StringPart::Variable(name),
+                expr => StringPart::Expression(expr),
+            },
+            _ => unreachable!(),
+        });
+    }
+    Ok(Expression::FormattedString(parts))
+}
+
+// The operator chain helpers below are kept out of `build_expression`, whose
+// frame size bounds how deeply nested a program can be parsed.
+
+/// Applies `op` to `expr` and `right`, where `previous` is the operator before it
+/// in the same chain. A comparison binds tighter than a `??` just before it.
+fn push_operator(
+    expr: Expression,
+    op: BinaryOp,
+    right: Expression,
+    previous: Option<BinaryOp>,
+) -> Expression {
+    match expr {
+        Expression::Binary { op: BinaryOp::Coalesce, left, right: last }
+            if previous == Some(BinaryOp::Coalesce) && op != BinaryOp::Coalesce =>
+        {
+            let right = Box::new(Expression::Binary { op, left: last, right: Box::new(right) });
+            Expression::Binary { op: BinaryOp::Coalesce, left, right }
+        }
+        expr => Expression::Binary { op, left: Box::new(expr), right: Box::new(right) },
+    }
+}
+
+fn build_unary(pair: Pair<Rule>) -> Result<Expression, ParseError> {
+    let mut inner = pair.into_inner();
+    let op = match inner.next().unwrap().as_str() {
+        "-" => UnaryOp::Neg,
+        _ => UnaryOp::Plus,
+    };
+    let mut operand = build_expression(inner.next().unwrap())?;
+    for accessor in inner {
+        operand = push_accessor(operand, accessor, false);
+    }
+    Ok(Expression::Unary { op, operand: Box::new(operand) })
+}
+
+/// Applies a `.key`/`?.key` accessor to the last factor of `expr`, which is its
+/// right operand when `expr` was `chained` from several factors.
+fn push_accessor(expr: Expression, accessor: Pair<Rule>, chained: bool) -> Expression {
+    match expr {
+        Expression::Binary { op, left, right } if chained => {
+            let right = Box::new(push_accessor(*right, accessor, false));
+            Expression::Binary { op, left, right }
+        }
+        object => {
+            let optional = accessor.as_str().starts_with('?');
+            let key = accessor.into_inner().next().unwrap().as_str().to_string();
+            Expression::Property { object: Box::new(object), key, optional }
+        }
+    }
+}
+
+fn binary_op(symbol: &str) -> BinaryOp {
+    match symbol {
+        "+" => BinaryOp::Add,
+        "-" => BinaryOp::Sub,
+        "*" => BinaryOp::Mul,
+        "/" => BinaryOp::Div,
+        "%" => BinaryOp::Mod,
+        "==" => BinaryOp::Eq,
+        "!=" => BinaryOp::Ne,
+        "<" => BinaryOp::Lt,
+        "<=" => BinaryOp::Le,
+        ">" => BinaryOp::Gt,
+        ">=" => BinaryOp::Ge,
+        _ => BinaryOp::Coalesce,
+    }
+}
+
 fn build_literal(pair: Pair<Rule>) -> Result<Expression, ParseError> {
-    let inner = pair.into_inner().next().unwrap();
+    build_scalar(pair.into_inner().next().unwrap())
+}
+
+fn build_scalar(inner: Pair<Rule>) -> Result<Expression, ParseError> {
     match inner.as_rule() {
         Rule::string => {
             // Extract the content between quotes
@@ -384,6 +954,7 @@ fn build_literal(pair: Pair<Rule>) -> Result<Expression, ParseError> {
         Rule::integer => Ok(Expression::Integer(inner.as_str().parse().unwrap())),
         Rule::float => Ok(Expression::Float(inner.as_str().parse().unwrap())),
         Rule::boolean => Ok(Expression::Boolean(inner.as_str().parse().unwrap())),
+        Rule::null => Ok(Expression::Null),
         _ => unreachable!("Unexpected literal rule: {:?}", inner.as_rule()),
     }
 }
@@ -3,7 +3,7 @@
 use crate::parser::{Expression, NodeDeclaration, Pattern};
 use crate::types::{Edge, Graph, Node};
 use serde_json::Value;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 
 #[derive(Debug, Clone)]
 pub struct Rule {
@@ -12,12 +12,145 @@ pub struct Rule {
     pub rhs: Pattern,
 }
 
-#[derive(Debug)]
-struct Match {
-    node_mapping: HashMap<String, String>, // Pattern node ID -> Graph node ID
+/// A match of a rule's LHS pattern in a graph.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Match {
+    /// LHS pattern node ID -> graph node ID it is bound to. The LHS edges connect
+    /// the bound nodes accordingly.
+    pub node_mapping: HashMap<String, String>,
 }
 
-fn expression_to_value(expr: &Expression) -> Result<Value, String> {
+/// Candidate and adjacency index used to find LHS matches without rescanning the graph.
+///
+/// It is built once per [`Rule::apply`] call and kept up to date as transformations
+/// delete, update, and create nodes and edges, so later iterations only pay for
+/// what changed.
+struct MatchIndex {
+    /// For each LHS pattern node, the graph nodes satisfying its type and attributes.
+    candidates: Vec<BTreeSet<String>>,
+    /// Graph node ID -> IDs of nodes sharing an edge with it, in either direction.
+    neighbors: HashMap<String, BTreeSet<String>>,
+    /// `(source, target, directed)` of every graph edge -> metadata of each such edge.
+    edges: HashMap<(String, String, bool), Vec<HashMap<String, Value>>>,
+    /// Lowest suffix that may still be free for a `new_edge_N` ID.
+    next_edge_suffix: usize,
+}
+
+impl MatchIndex {
+    fn new(rule: &Rule, graph: &Graph) -> Result<Self, String> {
+        let mut index = MatchIndex {
+            candidates: vec![BTreeSet::new(); rule.lhs.nodes.len()],
+            neighbors: HashMap::new(),
+            edges: HashMap::new(),
+            next_edge_suffix: 0,
+        };
+        for id in graph.nodes.keys() {
+            index.refresh_node(rule, graph, id)?;
+        }
+        for edge in graph.edges.values() {
+            index.add_edge(&edge.source, &edge.target, edge.directed, &edge.metadata);
+        }
+        Ok(index)
+    }
+
+    /// Re-evaluates which pattern nodes the graph node `id` can stand for.
+    fn refresh_node(&mut self, rule: &Rule, graph: &Graph, id: &str) -> Result<(), String> {
+        let Some(g_node) = graph.get_node(id) else {
+            return self.remove_node(id);
+        };
+        for (p_node, candidates) in rule.lhs.nodes.iter().zip(&mut self.candidates) {
+            if node_matches(g_node, p_node)? {
+                candidates.insert(id.to_string());
+            } else {
+                candidates.remove(id);
+            }
+        }
+        Ok(())
+    }
+
+    fn remove_node(&mut self, id: &str) -> Result<(), String> {
+        for candidates in &mut self.candidates {
+            candidates.remove(id);
+        }
+        Ok(())
+    }
+
+    fn add_edge(
+        &mut self,
+        source: &str,
+        target: &str,
+        directed: bool,
+        metadata: &HashMap<String, Value>,
+    ) {
+        self.edges
+            .entry((source.to_string(), target.to_string(), directed))
+            .or_default()
+            .push(metadata.clone());
+        self.neighbors
+            .entry(source.to_string())
+            .or_default()
+            .insert(target.to_string());
+        self.neighbors
+            .entry(target.to_string())
+            .or_default()
+            .insert(source.to_string());
+    }
+
+    /// Whether a graph edge connects `source` to `target` with exactly this directedness.
+    fn contains(&self, source: &str, target: &str, directed: bool) -> bool {
+        self.edges
+            .contains_key(&(source.to_string(), target.to_string(), directed))
+    }
+
+    /// Whether the LHS edge `constraint` is satisfied between `source` and `target`.
+    /// Undirected pattern edges match graph edges in either direction.
+    fn has_edge(&self, source: &str, target: &str, constraint: &EdgeConstraint) -> bool {
+        let matching = |source: &str, target: &str, directed: bool| {
+            self.edges
+                .get(&(source.to_string(), target.to_string(), directed))
+                .is_some_and(|edges| edges.iter().any(|metadata| constraint.accepts(metadata)))
+        };
+        let forward = matching(source, target, true) || matching(source, target, false);
+        let backward = matching(target, source, true) || matching(target, source, false);
+        forward || (!constraint.directed && backward)
+    }
+
+    /// Returns an unused `new_edge_N` ID, like [`Graph::generate_unique_edge_id`] but
+    /// without rescanning from zero, since rules never delete edges.
+    fn next_edge_id(&mut self, graph: &Graph) -> String {
+        loop {
+            let id = format!("new_edge_{}", self.next_edge_suffix);
+            self.next_edge_suffix += 1;
+            if !graph.edges.contains_key(&id) {
+                return id;
+            }
+        }
+    }
+}
+
+/// An LHS edge expressed as positions in the LHS node list.
+struct EdgeConstraint {
+    source: usize,
+    target: usize,
+    directed: bool,
+    /// Required edge metadata; `None` values come from `[key=*]` and only require the key.
+    attributes: Vec<(String, Option<Value>)>,
+}
+
+impl EdgeConstraint {
+    /// Whether a graph edge with this metadata satisfies the pattern's attributes.
+    fn accepts(&self, metadata: &HashMap<String, Value>) -> bool {
+        self.attributes.iter().all(|(key, expected)| {
+            metadata
+                .get(key)
+                .is_some_and(|value| expected.as_ref().is_none_or(|expected| value == expected))
+        })
+    }
+}
+
+/// Evaluates a rule attribute value. Attribute variables resolve to their value in
+/// `bindings`; bare identifiers are strings.
+fn expression_to_value(expr: &Expression, bindings: &HashMap<String, Value>) -> Result<Value, String> {
     match expr {
         Expression::StringLiteral(s) => Ok(Value::String(s.clone())),
         Expression::Integer(i) => Ok(Value::Number((*i).into())),
@@ -25,165 +158,287 @@ fn expression_to_value(expr: &Expression) -> Result<Value, String> {
             serde_json::Number::from_f64(*f).ok_or_else(|| "Invalid float value".to_string())?,
         )),
         Expression::Boolean(b) => Ok(Value::Bool(*b)),
+        Expression::Null => Ok(Value::Null),
         Expression::Identifier(s) => Ok(Value::String(s.clone())), // Treat identifiers in RHS as strings
+        Expression::Variable(name) => bindings
+            .get(name)
+            .cloned()
+            .ok_or_else(|| format!("Attribute variable '${name}' is not bound by the rule LHS")),
         Expression::FormattedString(_) => {
             Err("Formatted strings are not supported in rule RHS attributes".to_string())
         }
+        Expression::Unary { op, operand } => {
+            crate::evaluate_unary(*op, &expression_to_value(operand, bindings)?)
+        }
+        Expression::Binary { op, left, right } => crate::evaluate_binary(
+            *op,
+            &expression_to_value(left, bindings)?,
+            &expression_to_value(right, bindings)?,
+        ),
+        Expression::Call { name, .. } => {
+            Err(format!("Function calls are not supported in rule attributes: {name}"))
+        }
+        Expression::Property { .. } => {
+            Err(format!("Property access is not supported in rule attributes: {expr}"))
+        }
+        Expression::Match { .. } => {
+            Err(format!("Match expressions are not supported in rule attributes: {expr}"))
+        }
+        Expression::Wildcard => {
+            Err("Wildcard '*' is only allowed in rule LHS attributes".to_string())
+        }
+        Expression::Spread(_) => {
+            Err(format!("Spread '{expr}' is only allowed in function call arguments"))
+        }
+        Expression::Array(items) => items
+            .iter()
+            .map(|item| expression_to_value(item, bindings))
+            .collect::<Result<_, _>>()
+            .map(Value::Array),
+        Expression::Object(pairs) => pairs
+            .iter()
+            .map(|(key, value)| Ok((key.clone(), expression_to_value(value, bindings)?)))
+            .collect::<Result<_, String>>()
+            .map(Value::Object),
     }
 }
 
 impl Rule {
     /// Applies the rule to the graph for a specified number of iterations.
+    ///
+    /// Each iteration rewrites a set of non-overlapping matches, chosen greedily in
+    /// node ID order, and new edges are numbered in the order they are created, so
+    /// applying a rule to the same graph always gives the same result.
     pub fn apply(&self, graph: &mut Graph, iterations: usize) -> Result<(), String> {
+        self.apply_with(graph, iterations, |_| Ok(()))
+    }
+
+    /// Applies the rule like [`Rule::apply`], calling `after_iteration` with the graph
+    /// after every iteration that rewrote something. An error from the callback stops
+    /// the application and is returned.
+    pub fn apply_with(
+        &self,
+        graph: &mut Graph,
+        iterations: usize,
+        after_iteration: impl FnMut(&Graph) -> Result<(), String>,
+    ) -> Result<(), String> {
+        self.apply_observed(graph, iterations, |_, _| {}, after_iteration)
+    }
+
+    /// Applies the rule like [`Rule::apply`], calling `observer` with each match and
+    /// the graph it was found in, right before the match is rewritten.
+    pub fn apply_with_observer(
+        &self,
+        graph: &mut Graph,
+        iterations: usize,
+        observer: impl FnMut(&Match, &Graph),
+    ) -> Result<(), String> {
+        self.apply_observed(graph, iterations, observer, |_| Ok(()))
+    }
+
+    fn apply_observed(
+        &self,
+        graph: &mut Graph,
+        iterations: usize,
+        mut observer: impl FnMut(&Match, &Graph),
+        mut after_iteration: impl FnMut(&Graph) -> Result<(), String>,
+    ) -> Result<(), String> {
+        if iterations == 0 {
+            return Ok(());
+        }
+        let constraints = self.edge_constraints()?;
+        let mut index = MatchIndex::new(self, graph)?;
+
         for _ in 0..iterations {
-            let matches = self.find_matches(graph)?;
+            let matches = self.find_indexed_matches(&index, &constraints);
 
             if matches.is_empty() {
                 break; // No more matches found, stop applying.
             }
 
             for m in matches {
-                self.apply_transformation(graph, &m)?;
+                observer(&m, graph);
+                self.apply_transformation(graph, &m, &mut index)?;
             }
+            after_iteration(graph)?;
         }
         Ok(())
     }
 
-    /// Finds all non-overlapping matches of the LHS pattern in the graph.
-    fn find_matches(&self, graph: &Graph) -> Result<Vec<Match>, String> {
-        let mut all_matches = Vec::new();
-        let mut used_graph_nodes = HashSet::new();
-
-        let p_nodes = &self.lhs.nodes;
-        if p_nodes.is_empty() {
-            return Ok(all_matches);
-        }
-
-        let mut potential_matches = Vec::new();
-        self.find_potential_matches_recursive(
-            graph,
-            p_nodes,
-            &mut HashMap::new(),
-            &mut used_graph_nodes,
-            &mut potential_matches,
-            0,
-        )?;
-
-        // Filter for valid matches that satisfy edge constraints
-        for potential_match in potential_matches {
-            if self.is_valid_match(graph, &potential_match)? {
-                // Add to results and mark nodes as used
-                let mut is_overlapping = false;
-                for node_id in potential_match.values() {
-                    if used_graph_nodes.contains(node_id) {
-                        is_overlapping = true;
-                        break;
-                    }
-                }
+    /// Returns the matches the next application of the rule would rewrite, without
+    /// modifying the graph.
+    pub fn find_matches(&self, graph: &Graph) -> Result<Vec<Match>, String> {
+        let constraints = self.edge_constraints()?;
+        let index = MatchIndex::new(self, graph)?;
+        Ok(self.find_indexed_matches(&index, &constraints))
+    }
 
-                if !is_overlapping {
-                    for node_id in potential_match.values() {
-                        used_graph_nodes.insert(node_id.clone());
-                    }
-                    all_matches.push(Match {
-                        node_mapping: potential_match,
-                    });
-                }
-            }
+    /// Resolves the LHS edges to positions in the LHS node list.
+    fn edge_constraints(&self) -> Result<Vec<EdgeConstraint>, String> {
+        let position = |expr: &Expression| {
+            let id = expr.to_string();
+            self.lhs
+                .nodes
+                .iter()
+                .position(|n| n.id.to_string() == id)
+                .ok_or_else(|| "Invalid LHS pattern".to_string())
+        };
+        if self.lhs.nodes.is_empty() {
+            return Ok(Vec::new());
         }
-
-        Ok(all_matches)
+        self.lhs
+            .edges
+            .iter()
+            .map(|p_edge| {
+                let attributes = p_edge
+                    .attributes
+                    .iter()
+                    .map(|(key, expr)| {
+                        let expected = match expr {
+                            Expression::Wildcard => None,
+                            Expression::Variable(name) => {
+                                return Err(format!(
+                                    "Attribute variable '${name}' can only be bound by LHS nodes"
+                                ))
+                            }
+                            expr => Some(expression_to_value(expr, &HashMap::new())?),
+                        };
+                        Ok((key.clone(), expected))
+                    })
+                    .collect::<Result<_, String>>()?;
+                Ok(EdgeConstraint {
+                    source: position(&p_edge.source)?,
+                    target: position(&p_edge.target)?,
+                    directed: p_edge.directed,
+                    attributes,
+                })
+            })
+            .collect()
     }
 
-    /// Recursively finds all possible node mappings (potential matches) using backtracking.
-    fn find_potential_matches_recursive(
+    /// Finds non-overlapping matches of the LHS pattern, greedily in node ID order.
+    fn find_indexed_matches(
         &self,
-        graph: &Graph,
-        p_nodes: &[NodeDeclaration],
-        current_mapping: &mut HashMap<String, String>,
-        used_graph_nodes: &mut HashSet<String>,
-        results: &mut Vec<HashMap<String, String>>,
-        p_node_index: usize,
-    ) -> Result<(), String> {
-        if p_node_index == p_nodes.len() {
-            results.push(current_mapping.clone());
-            return Ok(());
+        index: &MatchIndex,
+        constraints: &[EdgeConstraint],
+    ) -> Vec<Match> {
+        let mut all_matches = Vec::new();
+        if self.lhs.nodes.is_empty() {
+            return all_matches;
         }
 
-        let p_node = &p_nodes[p_node_index];
-        let p_node_id = p_node.id.to_string();
-
-        for g_node_id in graph.nodes.keys() {
-            if !used_graph_nodes.contains(g_node_id) && !current_mapping.values().any(|v| v == g_node_id)
-                && self.node_matches(graph, g_node_id, p_node)? {
-                    current_mapping.insert(p_node_id.clone(), g_node_id.clone());
-
-                    self.find_potential_matches_recursive(
-                        graph,
-                        p_nodes,
-                        current_mapping,
-                        used_graph_nodes,
-                        results,
-                        p_node_index + 1,
-                    )?;
+        let mut used_graph_nodes = HashSet::new();
+        let mut assignment: Vec<&str> = Vec::with_capacity(self.lhs.nodes.len());
 
-                    current_mapping.remove(&p_node_id); // Backtrack
-                }
+        // Once a first-position candidate fails to complete a match, it cannot succeed
+        // later either, since the set of used nodes only grows.
+        for first in &index.candidates[0] {
+            if used_graph_nodes.contains(first.as_str()) {
+                continue;
+            }
+            assignment.clear();
+            assignment.push(first);
+            if self.extend_match(index, constraints, &used_graph_nodes, &mut assignment) {
+                let node_mapping = self
+                    .lhs
+                    .nodes
+                    .iter()
+                    .zip(&assignment)
+                    .map(|(p_node, g_id)| (p_node.id.to_string(), g_id.to_string()))
+                    .collect();
+                used_graph_nodes.extend(assignment.iter().copied());
+                all_matches.push(Match { node_mapping });
+            }
         }
-        Ok(())
-    }
-
-    /// Checks if a potential node mapping also satisfies the edge constraints of the pattern.
-    fn is_valid_match(&self, graph: &Graph, node_mapping: &HashMap<String, String>) -> Result<bool, String> {
-        for p_edge in &self.lhs.edges {
-            let p_source_id = p_edge.source.to_string();
-            let p_target_id = p_edge.target.to_string();
 
-            let g_source_id = node_mapping.get(&p_source_id).ok_or("Invalid LHS pattern")?;
-            let g_target_id = node_mapping.get(&p_target_id).ok_or("Invalid LHS pattern")?;
+        all_matches
+    }
 
-            let edge_exists = graph.edges.values().any(|g_edge| {
-                (g_edge.source == *g_source_id && g_edge.target == *g_target_id) ||
-                (!p_edge.directed && g_edge.source == *g_target_id && g_edge.target == *g_source_id)
-            });
+    /// Backtracking search assigning graph nodes to the remaining pattern positions.
+    fn extend_match<'a>(
+        &self,
+        index: &'a MatchIndex,
+        constraints: &[EdgeConstraint],
+        used_graph_nodes: &HashSet<&str>,
+        assignment: &mut Vec<&'a str>,
+    ) -> bool {
+        let position = assignment.len();
+        if position == self.lhs.nodes.len() {
+            return true;
+        }
 
-            if !edge_exists {
-                return Ok(false);
+        // If this position is connected to an already assigned one, only that node's
+        // neighbors can match; otherwise fall back to every candidate.
+        let anchor = constraints.iter().find_map(|c| {
+            if c.source == position && c.target < position {
+                Some(assignment[c.target])
+            } else if c.target == position && c.source < position {
+                Some(assignment[c.source])
+            } else {
+                None
+            }
+        });
+        let pool: Box<dyn Iterator<Item = &'a String>> = match anchor {
+            Some(anchor) => match index.neighbors.get(anchor) {
+                Some(neighbors) => Box::new(
+                    neighbors
+                        .iter()
+                        .filter(|id| index.candidates[position].contains(*id)),
+                ),
+                None => return false,
+            },
+            None => Box::new(index.candidates[position].iter()),
+        };
+
+        for g_node_id in pool {
+            let g_node_id = g_node_id.as_str();
+            if used_graph_nodes.contains(g_node_id) || assignment.contains(&g_node_id) {
+                continue;
             }
+            assignment.push(g_node_id);
+            let edges_ok = constraints
+                .iter()
+                .filter(|c| c.source.max(c.target) == position)
+                .all(|c| index.has_edge(assignment[c.source], assignment[c.target], c));
+            if edges_ok && self.extend_match(index, constraints, used_graph_nodes, assignment) {
+                return true;
+            }
+            assignment.pop(); // Backtrack
         }
-        Ok(true)
+        false
     }
 
-    /// Checks if a graph node matches a pattern node's criteria (type, attributes).
-    fn node_matches( &self, graph: &Graph, graph_node_id: &str, p_node: &NodeDeclaration) -> Result<bool, String> {
-        let g_node = graph.get_node(graph_node_id).ok_or("Internal error: Node disappeared")?;
-
-        println!("Checking if node '{}' (type: '{}') matches pattern node '{}' (type: {:?})",
-                 graph_node_id, g_node.r#type, p_node.id, p_node.node_type);
-
-        // Check type
-        if let Some(p_type_expr) = &p_node.node_type {
-            let p_type_str = p_type_expr.to_string();
-            if g_node.r#type != p_type_str {
-                return Ok(false);
-            }
-        }
-        // Check attributes
-        for (p_key, p_val_expr) in &p_node.attributes {
-            if let Some(g_val) = g_node.metadata.get(p_key) {
-                if g_val == &expression_to_value(p_val_expr)? {
-                    continue;
+    /// Collects the values of the attribute variables, e.g. `$C` in `node N [count=$C]`,
+    /// from the nodes bound by a match.
+    fn bind_variables(&self, graph: &Graph, m: &Match) -> HashMap<String, Value> {
+        let mut bindings = HashMap::new();
+        for p_node in &self.lhs.nodes {
+            let Some(g_node) = m
+                .node_mapping
+                .get(&p_node.id.to_string())
+                .and_then(|id| graph.get_node(id))
+            else {
+                continue;
+            };
+            for (key, expr) in &p_node.attributes {
+                if let (Expression::Variable(variable), Some(value)) = (expr, g_node.metadata.get(key)) {
+                    bindings.insert(variable.clone(), value.clone());
                 }
             }
-            return Ok(false);
         }
-        println!("  Match successful!");
-        Ok(true)
+        bindings
     }
 
-
     /// Applies the RHS transformation based on a match.
-    fn apply_transformation(&self, graph: &mut Graph, m: &Match) -> Result<(), String> {
+    fn apply_transformation(
+        &self,
+        graph: &mut Graph,
+        m: &Match,
+        index: &mut MatchIndex,
+    ) -> Result<(), String> {
+        // Read attribute variables before the matched nodes are modified
+        let bindings = self.bind_variables(graph, m);
+
         // --- Deletion Phase ---
         let lhs_nodes: HashSet<_> = self.lhs.nodes.iter().map(|n| n.id.to_string()).collect();
         let rhs_nodes: HashSet<_> = self.rhs.nodes.iter().map(|n| n.id.to_string()).collect();
@@ -191,6 +446,7 @@ impl Rule {
         for p_node_id in nodes_to_delete {
             if let Some(g_node_id) = m.node_mapping.get(p_node_id) {
                 graph.remove_node(g_node_id);
+                index.remove_node(g_node_id)?;
             }
         }
 
@@ -201,7 +457,7 @@ impl Rule {
             let p_node_id = p_node.id.to_string();
             let mut metadata = HashMap::new();
             for (key, val_expr) in &p_node.attributes {
-                metadata.insert(key.clone(), expression_to_value(val_expr)?);
+                metadata.insert(key.clone(), expression_to_value(val_expr, &bindings)?);
             }
 
             if let Some(g_node_id) = m.node_mapping.get(&p_node_id) {
@@ -215,6 +471,7 @@ impl Rule {
                     }
                     node.metadata.extend(metadata);
                 }
+                index.refresh_node(self, graph, g_node_id)?;
             } else {
                 // This is a new node declared only in the RHS.
                 // Treat its ID as a literal ID in the graph.
@@ -223,6 +480,7 @@ impl Rule {
                     let node_type = p_node.node_type.as_ref().map(|e| e.to_string()).unwrap_or_default();
                     let new_node = Node::new().with_type(node_type).with_metadata_map(metadata);
                     graph.add_node(new_g_node_id.clone(), new_node);
+                    index.refresh_node(self, graph, &new_g_node_id)?;
                 }
                 // Add this new/referenced node to a temporary mapping for edge creation.
                 rhs_node_mapping.insert(p_node_id, new_g_node_id);
@@ -237,13 +495,14 @@ impl Rule {
             let target_g_id = rhs_node_mapping.get(&target_p_id).ok_or(format!("RHS target node '{target_p_id}' not found in mapping"))?.clone();
 
             // Check if an equivalent edge already exists
-            let edge_exists = graph.edges.values().any(|g_edge| {
-                (g_edge.source == source_g_id && g_edge.target == target_g_id && g_edge.directed == p_edge.directed) ||
-                (!p_edge.directed && g_edge.source == target_g_id && g_edge.target == source_g_id)
-            });
+            let edge_exists = index.contains(&source_g_id, &target_g_id, p_edge.directed)
+                || (!p_edge.directed
+                    && (index.contains(&target_g_id, &source_g_id, true)
+                        || index.contains(&target_g_id, &source_g_id, false)));
 
             if !edge_exists {
-                let id = graph.generate_unique_edge_id("new_edge");
+                let id = index.next_edge_id(graph);
+                index.add_edge(&source_g_id, &target_g_id, p_edge.directed, &HashMap::new());
                 graph.add_edge(id, Edge::new(source_g_id, target_g_id, p_edge.directed));
             }
         }
@@ -251,3 +510,28 @@ impl Rule {
         Ok(())
     }
 }
+
+/// Checks if a graph node matches a pattern node's criteria (type, attributes).
+fn node_matches(g_node: &Node, p_node: &NodeDeclaration) -> Result<bool, String> {
+    // Check type
+    if let Some(p_type_expr) = &p_node.node_type {
+        let p_type_str = p_type_expr.to_string();
+        if g_node.r#type != p_type_str {
+            return Ok(false);
+        }
+    }
+    // Check attributes. Wildcards and attribute variables only require the key.
+    for (p_key, p_val_expr) in &p_node.attributes {
+        if let Some(g_val) = g_node.metadata.get(p_key) {
+            let matches = match p_val_expr {
+                Expression::Wildcard | Expression::Variable(_) => true,
+                expr => g_val == &expression_to_value(expr, &HashMap::new())?,
+            };
+            if matches {
+                continue;
+            }
+        }
+        return Ok(false);
+    }
+    Ok(true)
+}
@@ -0,0 +1,141 @@
+//! # Output Schemas
+//!
+//! A [`Schema`] lists the metadata keys every node and edge of a graph must carry,
+//! together with the JSON type of each value. [`validate`] checks a graph against it
+//! and reports every violation rather than stopping at the first.
+
+use crate::types::Graph;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+/// JSON value types a metadata entry can be required to have.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueType {
+    String,
+    /// Any number, integer or floating point.
+    Number,
+    Integer,
+    Boolean,
+    Array,
+    Object,
+}
+
+impl ValueType {
+    fn matches(self, value: &Value) -> bool {
+        match self {
+            ValueType::String => value.is_string(),
+            ValueType::Number => value.is_number(),
+            ValueType::Integer => value.is_i64() || value.is_u64(),
+            ValueType::Boolean => value.is_boolean(),
+            ValueType::Array => value.is_array(),
+            ValueType::Object => value.is_object(),
+        }
+    }
+}
+
+impl fmt::Display for ValueType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ValueType::String => "string",
+            ValueType::Number => "number",
+            ValueType::Integer => "integer",
+            ValueType::Boolean => "boolean",
+            ValueType::Array => "array",
+            ValueType::Object => "object",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Required metadata keys and their types for nodes and edges.
+#[derive(Debug, Clone, Default)]
+pub struct Schema {
+    pub node_metadata: BTreeMap<String, ValueType>,
+    pub edge_metadata: BTreeMap<String, ValueType>,
+}
+
+impl Schema {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requires every node to have metadata `key` of type `value_type`.
+    pub fn require_node(mut self, key: &str, value_type: ValueType) -> Self {
+        self.node_metadata.insert(key.to_string(), value_type);
+        self
+    }
+
+    /// Requires every edge to have metadata `key` of type `value_type`.
+    pub fn require_edge(mut self, key: &str, value_type: ValueType) -> Self {
+        self.edge_metadata.insert(key.to_string(), value_type);
+        self
+    }
+}
+
+/// A single node or edge metadata entry that does not conform to a [`Schema`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaViolation {
+    /// `"node"` or `"edge"`.
+    pub element: &'static str,
+    pub id: String,
+    pub key: String,
+    pub expected: ValueType,
+    /// The offending value, or `None` if the key is missing.
+    pub found: Option<Value>,
+}
+
+impl fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.found {
+            Some(value) => write!(
+                f,
+                "{} '{}': '{}' should be a {}, found {value}",
+                self.element, self.id, self.key, self.expected
+            ),
+            None => write!(
+                f,
+                "{} '{}': missing '{}' ({})",
+                self.element, self.id, self.key, self.expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SchemaViolation {}
+
+/// Checks every node and edge of `graph` against `schema`.
+///
+/// Violations are sorted by element kind (nodes first), ID and key.
+pub fn validate(graph: &Graph, schema: &Schema) -> Vec<SchemaViolation> {
+    let mut violations = Vec::new();
+    let node_metadata = graph.nodes.iter().map(|(id, node)| (id, &node.metadata));
+    check_elements("node", node_metadata, &schema.node_metadata, &mut violations);
+    let edge_metadata = graph.edges.iter().map(|(id, edge)| (id, &edge.metadata));
+    check_elements("edge", edge_metadata, &schema.edge_metadata, &mut violations);
+    violations
+}
+
+fn check_elements<'a>(
+    element: &'static str,
+    elements: impl Iterator<Item = (&'a String, &'a HashMap<String, Value>)>,
+    required: &BTreeMap<String, ValueType>,
+    violations: &mut Vec<SchemaViolation>,
+) {
+    let mut elements: Vec<_> = elements.collect();
+    elements.sort_by_key(|(id, _)| *id);
+    for (id, metadata) in elements {
+        for (key, &expected) in required {
+            let found = metadata.get(key);
+            if found.is_none_or(|value| !expected.matches(value)) {
+                violations.push(SchemaViolation {
+                    element,
+                    id: id.clone(),
+                    key: key.clone(),
+                    expected,
+                    found: found.cloned(),
+                });
+            }
+        }
+    }
+}
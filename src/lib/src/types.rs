@@ -1,18 +1,24 @@
 //! Core data structures for representing graphs.
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
 
+/// Maps are serialized with their keys sorted, so the same graph always produces
+/// byte-identical JSON.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Graph {
+    #[serde(serialize_with = "serialize_sorted")]
     pub nodes: HashMap<String, Node>,
+    #[serde(serialize_with = "serialize_sorted")]
     pub edges: HashMap<String, Edge>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Node {
     pub r#type: String,
+    #[serde(serialize_with = "serialize_sorted")]
     pub metadata: HashMap<String, Value>,
 }
 
@@ -21,9 +27,196 @@ pub struct Edge {
     pub source: String,
     pub target: String,
     pub directed: bool,
+    #[serde(serialize_with = "serialize_sorted")]
     pub metadata: HashMap<String, Value>,
 }
 
+fn serialize_sorted<T: Serialize, S: Serializer>(
+    map: &HashMap<String, T>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    map.iter().collect::<BTreeMap<_, _>>().serialize(serializer)
+}
+
+/// How [`Graph::merge`] resolves node or edge IDs present in both graphs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MergePolicy {
+    /// The incoming node or edge replaces the existing one.
+    #[default]
+    Overwrite,
+    /// The existing node or edge is kept.
+    Skip,
+    /// The merge fails.
+    Error,
+}
+
+/// How [`Graph::to_value`] lays out nodes and edges.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GraphFormat {
+    /// Objects keyed by ID: `{"nodes": {"a": {...}}, ...}`. This is what the
+    /// engine outputs.
+    #[default]
+    Keyed,
+    /// Arrays sorted by ID, with the ID stored in an `id` field of each element:
+    /// `{"nodes": [{"id": "a", ...}], ...}`.
+    Array,
+}
+
+/// Converts graph JSON in either [`GraphFormat`] into the canonical keyed form.
+///
+/// Array elements are keyed by their `id` field, or by their position when they
+/// have none. Missing node `type`s default to `"default"`, missing edge
+/// `directed` flags to `false`, and missing `metadata` to an empty object, so both
+/// layouts of the same graph normalize to the same value. Values without
+/// `nodes`/`edges` sections get empty ones.
+pub fn normalize_graph(value: Value) -> Value {
+    let normalize_section = |section: Option<&Value>, defaults: &[(&str, Value)]| {
+        let entries: Vec<(String, Value)> = match section {
+            Some(Value::Object(items)) => items.clone().into_iter().collect(),
+            Some(Value::Array(items)) => items
+                .iter()
+                .enumerate()
+                .map(|(i, item)| {
+                    let mut item = item.clone();
+                    let id = match item.as_object_mut().and_then(|item| item.remove("id")) {
+                        Some(Value::String(id)) => id,
+                        Some(id) => id.to_string(),
+                        None => i.to_string(),
+                    };
+                    (id, item)
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        let items = entries
+            .into_iter()
+            .map(|(id, mut item)| {
+                if let Some(fields) = item.as_object_mut() {
+                    for (key, default) in defaults {
+                        fields.entry(*key).or_insert_with(|| default.clone());
+                    }
+                }
+                (id, item)
+            })
+            .collect();
+        Value::Object(items)
+    };
+
+    let empty = || Value::Object(serde_json::Map::new());
+    serde_json::json!({
+        "nodes": normalize_section(
+            value.get("nodes"),
+            &[("type", Value::from("default")), ("metadata", empty())],
+        ),
+        "edges": normalize_section(
+            value.get("edges"),
+            &[("directed", Value::Bool(false)), ("metadata", empty())],
+        ),
+    })
+}
+
+/// The differences between two graphs, as produced by [`Graph::diff`].
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct GraphDiff {
+    pub added_nodes: Vec<String>,
+    pub removed_nodes: Vec<String>,
+    pub changed_nodes: Vec<ElementChange>,
+    pub added_edges: Vec<String>,
+    pub removed_edges: Vec<String>,
+    pub changed_edges: Vec<ElementChange>,
+}
+
+/// A node or edge present in both graphs whose contents differ.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ElementChange {
+    pub id: String,
+    pub changes: Vec<FieldChange>,
+}
+
+/// A single changed field. Metadata entries are reported as `metadata.<key>`;
+/// `old`/`new` are `None` when the entry is missing on that side.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: String,
+    pub old: Option<Value>,
+    pub new: Option<Value>,
+}
+
+impl GraphDiff {
+    /// Returns true if the two graphs were identical.
+    pub fn is_empty(&self) -> bool {
+        self.added_nodes.is_empty()
+            && self.removed_nodes.is_empty()
+            && self.changed_nodes.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+            && self.changed_edges.is_empty()
+    }
+}
+
+impl FieldChange {
+    fn new(field: &str, old: Option<Value>, new: Option<Value>) -> Self {
+        FieldChange {
+            field: field.to_string(),
+            old,
+            new,
+        }
+    }
+}
+
+/// Splits the keys of two maps into added, removed, and changed entries.
+fn diff_maps<T>(
+    old: &HashMap<String, T>,
+    new: &HashMap<String, T>,
+    compare: impl Fn(&T, &T) -> Vec<FieldChange>,
+) -> (Vec<String>, Vec<String>, Vec<ElementChange>) {
+    let mut added: Vec<String> = new.keys().filter(|id| !old.contains_key(*id)).cloned().collect();
+    let mut removed: Vec<String> = old.keys().filter(|id| !new.contains_key(*id)).cloned().collect();
+    let mut changed: Vec<ElementChange> = old
+        .iter()
+        .filter_map(|(id, old_item)| {
+            let changes = compare(old_item, new.get(id)?);
+            (!changes.is_empty()).then(|| ElementChange {
+                id: id.clone(),
+                changes,
+            })
+        })
+        .collect();
+    added.sort();
+    removed.sort();
+    changed.sort_by(|a, b| a.id.cmp(&b.id));
+    (added, removed, changed)
+}
+
+fn diff_metadata(old: &HashMap<String, Value>, new: &HashMap<String, Value>) -> Vec<FieldChange> {
+    let keys: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+    keys.into_iter()
+        .filter(|key| old.get(*key) != new.get(*key))
+        .map(|key| {
+            FieldChange::new(
+                &format!("metadata.{key}"),
+                old.get(key).cloned(),
+                new.get(key).cloned(),
+            )
+        })
+        .collect()
+}
+
+/// Error returned by [`Graph::topological_sort`] when the graph is not acyclic.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CycleError {
+    /// Node IDs along the cycle, with the first node repeated at the end.
+    pub cycle: Vec<String>,
+}
+
+impl fmt::Display for CycleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Graph contains a cycle: {}", self.cycle.join(" -> "))
+    }
+}
+
+impl std::error::Error for CycleError {}
+
 impl Graph {
     pub fn new() -> Self {
         Graph {
@@ -40,6 +233,14 @@ impl Graph {
         self.edges.insert(id, edge);
     }
 
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
     pub fn get_node(&self, id: &str) -> Option<&Node> {
         self.nodes.get(id)
     }
@@ -56,6 +257,302 @@ impl Graph {
         serde_json::to_string_pretty(self)
     }
 
+    /// Serializes the graph to a JSON value in the given format.
+    pub fn to_value(&self, format: GraphFormat) -> Result<Value, serde_json::Error> {
+        let value = serde_json::to_value(self)?;
+        Ok(match format {
+            GraphFormat::Keyed => value,
+            GraphFormat::Array => {
+                let as_array = |key: &str| {
+                    let mut items: Vec<(String, Value)> = value[key]
+                        .as_object()
+                        .into_iter()
+                        .flatten()
+                        .map(|(id, item)| (id.clone(), item.clone()))
+                        .collect();
+                    items.sort_by(|(a, _), (b, _)| a.cmp(b));
+                    Value::Array(
+                        items
+                            .into_iter()
+                            .map(|(id, mut item)| {
+                                item["id"] = Value::String(id);
+                                item
+                            })
+                            .collect(),
+                    )
+                };
+                serde_json::json!({ "nodes": as_array("nodes"), "edges": as_array("edges") })
+            }
+        })
+    }
+
+    /// Like [`Graph::to_json`], but with nodes and edges laid out as `format`.
+    pub fn to_json_as(&self, format: GraphFormat) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(&self.to_value(format)?)
+    }
+
+    /// Returns the graph with `prefix` prepended to every node ID and edge ID.
+    ///
+    /// Edge endpoints are rewritten as well, so connectivity is preserved. This is
+    /// useful for namespacing subgraphs before merging them.
+    pub fn with_id_prefix(self, prefix: &str) -> Graph {
+        let nodes = self
+            .nodes
+            .into_iter()
+            .map(|(id, node)| (format!("{prefix}{id}"), node))
+            .collect();
+        let edges = self
+            .edges
+            .into_iter()
+            .map(|(id, mut edge)| {
+                edge.source = format!("{prefix}{}", edge.source);
+                edge.target = format!("{prefix}{}", edge.target);
+                (format!("{prefix}{id}"), edge)
+            })
+            .collect();
+        Graph { nodes, edges }
+    }
+
+    /// Merges the nodes and edges of `other` into this graph.
+    ///
+    /// `policy` decides what happens when a node or edge ID exists in both graphs.
+    /// With [`MergePolicy::Error`] the graph is left untouched if any ID collides.
+    pub fn merge(&mut self, other: Graph, policy: MergePolicy) -> Result<(), String> {
+        match policy {
+            MergePolicy::Overwrite => {
+                self.nodes.extend(other.nodes);
+                self.edges.extend(other.edges);
+            }
+            MergePolicy::Skip => {
+                for (id, node) in other.nodes {
+                    self.nodes.entry(id).or_insert(node);
+                }
+                for (id, edge) in other.edges {
+                    self.edges.entry(id).or_insert(edge);
+                }
+            }
+            MergePolicy::Error => {
+                let mut node_collisions: Vec<&String> =
+                    other.nodes.keys().filter(|id| self.nodes.contains_key(*id)).collect();
+                if !node_collisions.is_empty() {
+                    node_collisions.sort();
+                    return Err(format!("Node ID collision on merge: {node_collisions:?}"));
+                }
+                let mut edge_collisions: Vec<&String> =
+                    other.edges.keys().filter(|id| self.edges.contains_key(*id)).collect();
+                if !edge_collisions.is_empty() {
+                    edge_collisions.sort();
+                    return Err(format!("Edge ID collision on merge: {edge_collisions:?}"));
+                }
+                self.nodes.extend(other.nodes);
+                self.edges.extend(other.edges);
+            }
+        }
+        Ok(())
+    }
+
+    /// Contracts the edge `edge_id`, merging its target node into its source node.
+    ///
+    /// Every other edge incident to the target is rewired to the source. Edges that
+    /// would become self-loops because they connected the two endpoints are removed
+    /// along with the contracted edge. On metadata conflicts the source node's value
+    /// is kept; keys only present on the target are copied over. The graph is left
+    /// unchanged if the edge or either endpoint is missing.
+    pub fn contract_edge(&mut self, edge_id: &str) -> Result<(), String> {
+        let edge = self
+            .edges
+            .get(edge_id)
+            .ok_or_else(|| format!("Edge '{edge_id}' not found"))?;
+        let (source, target) = (edge.source.clone(), edge.target.clone());
+        if !self.nodes.contains_key(&source) {
+            return Err(format!("Source node '{source}' not found"));
+        }
+        if !self.nodes.contains_key(&target) {
+            return Err(format!("Target node '{target}' not found"));
+        }
+        self.edges.remove(edge_id);
+        if source == target {
+            return Ok(());
+        }
+        let target_node = self.nodes.remove(&target).unwrap();
+
+        let survivor = self.nodes.get_mut(&source).unwrap();
+        for (key, value) in target_node.metadata {
+            survivor.metadata.entry(key).or_insert(value);
+        }
+
+        self.edges.retain(|_, e| {
+            let joins_endpoints = (e.source == source && e.target == target)
+                || (e.source == target && e.target == source);
+            !joins_endpoints
+        });
+        for e in self.edges.values_mut() {
+            if e.source == target {
+                e.source = source.clone();
+            }
+            if e.target == target {
+                e.target = source.clone();
+            }
+        }
+        Ok(())
+    }
+
+    /// Builds the line graph: one node per edge of this graph, with an undirected edge
+    /// between two nodes whenever the corresponding edges share an endpoint.
+    ///
+    /// Line graph nodes take their IDs and metadata from the original edges and record
+    /// the original endpoints in `source` / `target` metadata. The edge joining `a` and
+    /// `b` (with `a < b`) has the ID `"{a}--{b}"`.
+    pub fn line_graph(&self) -> Graph {
+        let mut line = Graph::new();
+        let mut incident: HashMap<&str, BTreeSet<&str>> = HashMap::new();
+        for (id, edge) in &self.edges {
+            let node = Node::new()
+                .with_metadata_map(edge.metadata.clone())
+                .with_metadata("source".to_string(), Value::from(edge.source.clone()))
+                .with_metadata("target".to_string(), Value::from(edge.target.clone()));
+            line.add_node(id.clone(), node);
+            incident.entry(&edge.source).or_default().insert(id);
+            incident.entry(&edge.target).or_default().insert(id);
+        }
+        for edge_ids in incident.values() {
+            let edge_ids: Vec<&str> = edge_ids.iter().copied().collect();
+            for (i, a) in edge_ids.iter().enumerate() {
+                for b in &edge_ids[i + 1..] {
+                    line.add_edge(
+                        format!("{a}--{b}"),
+                        Edge::new(a.to_string(), b.to_string(), false),
+                    );
+                }
+            }
+        }
+        line
+    }
+
+    /// Compares this graph against `other`, reporting what `other` adds, removes, or changes.
+    ///
+    /// All ID lists are sorted so the result is deterministic.
+    pub fn diff(&self, other: &Graph) -> GraphDiff {
+        let (added_nodes, removed_nodes, changed_nodes) =
+            diff_maps(&self.nodes, &other.nodes, |old, new| {
+                let mut changes = Vec::new();
+                if old.r#type != new.r#type {
+                    changes.push(FieldChange::new(
+                        "type",
+                        Some(Value::from(old.r#type.clone())),
+                        Some(Value::from(new.r#type.clone())),
+                    ));
+                }
+                changes.extend(diff_metadata(&old.metadata, &new.metadata));
+                changes
+            });
+        let (added_edges, removed_edges, changed_edges) =
+            diff_maps(&self.edges, &other.edges, |old, new| {
+                let mut changes = Vec::new();
+                if old.source != new.source {
+                    changes.push(FieldChange::new(
+                        "source",
+                        Some(Value::from(old.source.clone())),
+                        Some(Value::from(new.source.clone())),
+                    ));
+                }
+                if old.target != new.target {
+                    changes.push(FieldChange::new(
+                        "target",
+                        Some(Value::from(old.target.clone())),
+                        Some(Value::from(new.target.clone())),
+                    ));
+                }
+                if old.directed != new.directed {
+                    changes.push(FieldChange::new(
+                        "directed",
+                        Some(Value::from(old.directed)),
+                        Some(Value::from(new.directed)),
+                    ));
+                }
+                changes.extend(diff_metadata(&old.metadata, &new.metadata));
+                changes
+            });
+
+        GraphDiff {
+            added_nodes,
+            removed_nodes,
+            changed_nodes,
+            added_edges,
+            removed_edges,
+            changed_edges,
+        }
+    }
+
+    /// Returns the node IDs in topological order, treating every edge as directed
+    /// from `source` to `target`.
+    ///
+    /// Ties are broken by node ID so the order is deterministic. If the graph
+    /// contains a cycle, the returned [`CycleError`] lists the nodes along one of them.
+    pub fn topological_sort(&self) -> Result<Vec<String>, CycleError> {
+        let mut in_degree: HashMap<&str, usize> =
+            self.nodes.keys().map(|id| (id.as_str(), 0)).collect();
+        let mut successors: HashMap<&str, Vec<&str>> = HashMap::new();
+        for edge in self.edges.values() {
+            *in_degree.entry(edge.target.as_str()).or_insert(0) += 1;
+            in_degree.entry(edge.source.as_str()).or_insert(0);
+            successors
+                .entry(edge.source.as_str())
+                .or_default()
+                .push(edge.target.as_str());
+        }
+
+        let mut ready: BTreeSet<&str> = in_degree
+            .iter()
+            .filter(|(_, &d)| d == 0)
+            .map(|(&id, _)| id)
+            .collect();
+        let mut order = Vec::with_capacity(in_degree.len());
+
+        while let Some(id) = ready.pop_first() {
+            order.push(id.to_string());
+            for &next in successors.get(id).into_iter().flatten() {
+                let d = in_degree.get_mut(next).unwrap();
+                *d -= 1;
+                if *d == 0 {
+                    ready.insert(next);
+                }
+            }
+        }
+
+        if order.len() == in_degree.len() {
+            return Ok(order);
+        }
+
+        // Every remaining node has an unprocessed predecessor, so walking backwards
+        // from any of them must eventually revisit a node.
+        let remaining: HashMap<&str, &str> = self
+            .edges
+            .values()
+            .filter(|e| in_degree[e.source.as_str()] > 0 && in_degree[e.target.as_str()] > 0)
+            .map(|e| (e.target.as_str(), e.source.as_str()))
+            .collect();
+        let mut current = *in_degree
+            .iter()
+            .filter(|(_, &d)| d > 0)
+            .map(|(id, _)| id)
+            .min()
+            .unwrap();
+        let mut path = vec![current];
+        loop {
+            current = remaining[current];
+            if let Some(pos) = path.iter().position(|&id| id == current) {
+                // `path` follows predecessors, so reverse it to get edge direction.
+                let cycle = std::iter::once(current)
+                    .chain(path[pos..].iter().rev().copied())
+                    .map(String::from)
+                    .collect();
+                return Err(CycleError { cycle });
+            }
+            path.push(current);
+        }
+    }
+
     /// Generates a unique node ID based on a prefix.
     pub fn generate_unique_node_id(&self, prefix: &str) -> String {
         let mut i = 0;
@@ -130,6 +627,20 @@ impl Edge {
         self.metadata.extend(metadata_map);
         self
     }
+
+    /// Sets the edge weight, stored under the `weight` metadata key.
+    /// Fails for NaN and infinite weights, which cannot be represented in JSON.
+    pub fn with_weight(self, weight: f64) -> Result<Self, String> {
+        match serde_json::Number::from_f64(weight) {
+            Some(n) => Ok(self.with_metadata("weight".to_string(), Value::Number(n))),
+            None => Err(format!("Edge weight must be finite, got {weight}")),
+        }
+    }
+
+    /// Returns the `weight` metadata entry, if present and numeric.
+    pub fn weight(&self) -> Option<f64> {
+        self.metadata.get("weight").and_then(Value::as_f64)
+    }
 }
 
 impl Default for Graph {
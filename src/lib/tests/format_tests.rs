@@ -0,0 +1,119 @@
+use graph_generation_language::format::{format_expression, format_ggl};
+use graph_generation_language::parser::{BinaryOp, Expression};
+use graph_generation_language::GGLEngine;
+
+const EXAMPLE: &str = r#"
+graph   example{
+  let size=3;let labels = ["a" , "b"];
+  let config = {"max load": 0.5, size};
+  let fallback=null??(size==3)??(1??2);
+  let weight=2*config?.size + (config.missing??1);
+  let mirrored=-size*2 + +size - -(size+1) + -(-1);
+  # The entry point.
+  node entry:gateway[weight=size*2+1,scaled=(size+1)*2, ratio=1.0];
+  for i in 0..size { node "n{i}" [label="Node {i + 1}"]; edge e: entry->"n{i}"; }
+  for name in labels { node name; }
+  while node_count<10 { node "extra_"+node_count; }
+  generate grid { rows: size; cols: 2; prefix: "g"; periodic: true; }
+  rule grow { lhs { node A [count=$C]; } rhs { node A [count=$C - (1 - 2)]; node B; edge A -- B; } }
+  apply grow 2 times;
+  node tail [kind=match(size){1=>"one",size=>"many",_=>"none",}, spread=hypot( ... [size,4])];
+}
+"#;
+
+#[test]
+fn test_format_is_canonical() {
+    let formatted = format_ggl(EXAMPLE).unwrap();
+    let expected = r#"graph example {
+    let size = 3;
+    let labels = ["a", "b"];
+    let config = {"max load": 0.5, size: size};
+    let fallback = null ?? size == 3 ?? (1 ?? 2);
+    let weight = 2 * config?.size + (config.missing ?? 1);
+    let mirrored = -size * 2 + +size - -(size + 1) + -(-1);
+    # The entry point.
+    node entry :gateway [weight=size * 2 + 1, scaled=(size + 1) * 2, ratio=1.0];
+    for i in 0..size {
+        node "n{i}" [label="Node {i + 1}"];
+        edge e: entry -> "n{i}";
+    }
+    for name in labels {
+        node name;
+    }
+    while node_count < 10 {
+        node "extra_" + node_count;
+    }
+    generate grid {
+        rows: size;
+        cols: 2;
+        prefix: "g";
+        periodic: true;
+    }
+    rule grow {
+        lhs {
+            node A [count=$C];
+        }
+        rhs {
+            node A [count=$C - (1 - 2)];
+            node B;
+            edge A -- B;
+        }
+    }
+    apply grow 2 times;
+    node tail [kind=match (size) { 1 => "one", size => "many", _ => "none" }, spread=hypot(...[size, 4])];
+}
+"#;
+    assert_eq!(formatted, expected);
+}
+
+#[test]
+fn test_format_is_idempotent_and_preserves_meaning() {
+    let formatted = format_ggl(EXAMPLE).unwrap();
+    assert_eq!(format_ggl(&formatted).unwrap(), formatted);
+
+    let original = GGLEngine::new().generate_from_ggl(EXAMPLE).unwrap();
+    let reformatted = GGLEngine::new().generate_from_ggl(&formatted).unwrap();
+    assert_eq!(original, reformatted);
+}
+
+#[test]
+fn test_format_refuses_plain_comments() {
+    let err = format_ggl("graph g {\n    node a; // trailing\n}").unwrap_err();
+    assert!(err.contains("comments, which would be lost (line 2, column 13)"));
+    let err = format_ggl("graph g { /* block */ node a; }").unwrap_err();
+    assert!(err.contains("line 1, column 11"));
+
+    // Comment markers inside strings are not comments
+    let formatted = format_ggl(r#"graph g { node a [url="http://x/*y*/"]; }"#).unwrap();
+    assert_eq!(formatted, "graph g {\n    node a [url=\"http://x/*y*/\"];\n}\n");
+}
+
+#[test]
+fn test_format_keeps_anonymous_graphs_anonymous() {
+    let formatted = format_ggl("graph{node a;}").unwrap();
+    assert_eq!(formatted, "graph {\n    node a;\n}\n");
+    assert_eq!(format_ggl(&formatted).unwrap(), formatted);
+    assert_eq!(format_ggl("graph {}").unwrap(), "graph {\n}\n");
+}
+
+#[test]
+fn test_format_long_operator_chain() {
+    // Longer than the parser accepts, to check the formatter does not recurse per term
+    let terms = 6000;
+    let mut chain = Expression::Integer(1);
+    for _ in 1..terms {
+        chain = Expression::Binary {
+            op: BinaryOp::Add,
+            left: Box::new(chain),
+            right: Box::new(Expression::Integer(1)),
+        };
+    }
+    let text = format_expression(&chain);
+    assert_eq!(text, vec!["1"; terms].join(" + "));
+}
+
+#[test]
+fn test_format_reports_parse_errors() {
+    let err = format_ggl("graph broken { node a }").unwrap_err();
+    assert!(err.starts_with("Parse error"));
+}
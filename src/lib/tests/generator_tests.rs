@@ -365,6 +365,170 @@ fn test_generate_barabasi_albert_m0() {
     assert!(graph.edges.is_empty());
 }
 
+#[test]
+fn test_generate_barabasi_albert_initial_clique() {
+    let mut params = HashMap::new();
+    params.insert("nodes".to_string(), Value::from(8));
+    params.insert("edges_per_node".to_string(), Value::from(2));
+    params.insert("initial_nodes".to_string(), Value::from(3));
+    let graph = generate_barabasi_albert(&params).unwrap();
+    assert_eq!(graph.nodes.len(), 8);
+    assert_eq!(graph.edges.len(), 3 + 5 * 2); // seed clique + m edges per added node
+
+    // The seed clique n0, n1, n2 must be fully connected
+    for (a, b) in [("n0", "n1"), ("n0", "n2"), ("n1", "n2")] {
+        assert!(graph.edges.values().any(|e| {
+            (e.source == a && e.target == b) || (e.source == b && e.target == a)
+        }));
+    }
+}
+
+#[test]
+fn test_generate_barabasi_albert_initial_nodes_too_small() {
+    let mut params = HashMap::new();
+    params.insert("nodes".to_string(), Value::from(8));
+    params.insert("edges_per_node".to_string(), Value::from(3));
+    params.insert("initial_nodes".to_string(), Value::from(2));
+    assert!(generate_barabasi_albert(&params).is_err());
+}
+
+#[test]
+fn test_generate_ring_lattice_degree() {
+    let mut params = HashMap::new();
+    params.insert("nodes".to_string(), Value::from(10));
+    params.insert("k".to_string(), Value::from(4));
+    let graph = generate_ring_lattice(&params).unwrap();
+    assert_eq!(graph.nodes.len(), 10);
+    assert_eq!(graph.edges.len(), 20); // n*k/2
+
+    for id in graph.nodes.keys() {
+        let degree = graph
+            .edges
+            .values()
+            .filter(|e| &e.source == id || &e.target == id)
+            .count();
+        assert_eq!(degree, 4, "node {id} should have degree k");
+    }
+}
+
+#[test]
+fn test_generate_ring_lattice_invalid_k() {
+    let mut params = HashMap::new();
+    params.insert("nodes".to_string(), Value::from(10));
+    params.insert("k".to_string(), Value::from(3));
+    assert!(generate_ring_lattice(&params).is_err());
+
+    params.insert("k".to_string(), Value::from(10));
+    assert!(generate_ring_lattice(&params).is_err());
+}
+
+#[test]
+fn test_generate_complete_bipartite() {
+    let mut params = HashMap::new();
+    params.insert("left".to_string(), Value::from(3));
+    params.insert("right".to_string(), Value::from(4));
+    let graph = generate_complete_bipartite(&params).unwrap();
+    assert_eq!(graph.nodes.len(), 7);
+    assert_eq!(graph.edges.len(), 12); // left*right
+
+    assert_eq!(graph.get_node("l0").unwrap().metadata["partition"], "left");
+    assert_eq!(graph.get_node("r0").unwrap().metadata["partition"], "right");
+
+    // No edge may connect two nodes of the same partition
+    for edge in graph.edges.values() {
+        let source_partition = &graph.get_node(&edge.source).unwrap().metadata["partition"];
+        let target_partition = &graph.get_node(&edge.target).unwrap().metadata["partition"];
+        assert_ne!(source_partition, target_partition);
+    }
+}
+
+#[test]
+fn test_generate_petersen() {
+    let graph = generate_petersen(&HashMap::new()).unwrap();
+    assert_eq!(graph.nodes.len(), 10);
+    assert_eq!(graph.edges.len(), 15);
+
+    for id in graph.nodes.keys() {
+        let degree = graph
+            .edges
+            .values()
+            .filter(|e| &e.source == id || &e.target == id)
+            .count();
+        assert_eq!(degree, 3, "node {id} should have degree 3");
+    }
+}
+
+#[test]
+fn test_generate_named() {
+    let mut params = HashMap::new();
+    params.insert("name".to_string(), Value::from("petersen"));
+    let graph = generate_named(&params).unwrap();
+    assert_eq!(graph.nodes.len(), 10);
+    assert_eq!(graph.edges.len(), 15);
+
+    params.insert("name".to_string(), Value::from("not_a_graph"));
+    assert!(generate_named(&params).is_err());
+    assert!(generate_named(&HashMap::new()).is_err());
+}
+
+#[test]
+fn test_directed_flag_honored_by_all_generators() {
+    let cases: Vec<(&str, Vec<(&str, Value)>)> = vec![
+        ("complete", vec![("nodes", Value::from(4))]),
+        ("path", vec![("nodes", Value::from(4))]),
+        ("cycle", vec![("nodes", Value::from(4))]),
+        ("grid", vec![("rows", Value::from(2)), ("cols", Value::from(3))]),
+        ("star", vec![("nodes", Value::from(4))]),
+        ("tree", vec![("branching", Value::from(2)), ("depth", Value::from(3))]),
+        (
+            "barabasi_albert",
+            vec![("nodes", Value::from(6)), ("edges_per_node", Value::from(2))],
+        ),
+        ("ring_lattice", vec![("nodes", Value::from(6)), ("k", Value::from(2))]),
+        ("complete_bipartite", vec![("left", Value::from(2)), ("right", Value::from(3))]),
+        ("petersen", vec![]),
+        ("named", vec![("name", Value::from("petersen"))]),
+    ];
+
+    for (name, base) in cases {
+        let generator = get_generator(name).unwrap();
+        for directed in [true, false] {
+            let mut params: HashMap<String, Value> =
+                base.iter().map(|(k, v)| (k.to_string(), v.clone())).collect();
+            params.insert("directed".to_string(), Value::from(directed));
+            let graph = generator(&params).unwrap();
+            assert!(!graph.edges.is_empty(), "{name} produced no edges");
+            assert!(
+                graph.edges.values().all(|e| e.directed == directed),
+                "{name} did not honor directed={directed}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_generator_metadata_templates() {
+    let mut params = HashMap::new();
+    params.insert("rows".to_string(), Value::from(2));
+    params.insert("cols".to_string(), Value::from(2));
+    params.insert("node_attrs".to_string(), serde_json::json!({"kind": "cell", "level": 1}));
+    params.insert("edge_attrs".to_string(), serde_json::json!({"weight": 0.5}));
+    let graph = generate_grid(&params).unwrap();
+
+    assert!(graph
+        .nodes
+        .values()
+        .all(|n| n.metadata["kind"] == "cell" && n.metadata["level"] == 1));
+    assert!(graph.edges.values().all(|e| e.weight() == Some(0.5)));
+
+    params.insert("edge_attrs".to_string(), serde_json::json!({"weight": "heavy"}));
+    let err = generate_grid(&params).unwrap_err();
+    assert!(err.contains("Edge weight must be a number, got \"heavy\""));
+
+    params.insert("node_attrs".to_string(), Value::from("cell"));
+    assert!(generate_grid(&params).is_err());
+}
+
 #[test]
 fn test_get_generator_valid() {
     assert!(get_generator("complete").is_some());
@@ -374,6 +538,10 @@ fn test_get_generator_valid() {
     assert!(get_generator("star").is_some());
     assert!(get_generator("tree").is_some());
     assert!(get_generator("barabasi_albert").is_some());
+    assert!(get_generator("ring_lattice").is_some());
+    assert!(get_generator("complete_bipartite").is_some());
+    assert!(get_generator("petersen").is_some());
+    assert!(get_generator("named").is_some());
 }
 
 #[test]
@@ -432,3 +600,38 @@ fn test_graph_merging() {
     let cycle_graph = generate_cycle(&params3).unwrap();
     assert_eq!(cycle_graph.edges.len(), 5);
 }
+
+#[test]
+fn test_generate_barabasi_albert_seeded() {
+    let mut params = HashMap::new();
+    params.insert("nodes".to_string(), Value::from(40));
+    params.insert("edges_per_node".to_string(), Value::from(3));
+    params.insert("seed".to_string(), Value::from(7));
+
+    let edge_set = |graph: &graph_generation_language::types::Graph| {
+        let mut edges: Vec<_> = graph
+            .edges
+            .iter()
+            .map(|(id, e)| (id.clone(), e.source.clone(), e.target.clone()))
+            .collect();
+        edges.sort();
+        edges
+    };
+
+    let first = generate_barabasi_albert(&params).unwrap();
+    let second = generate_barabasi_albert(&params).unwrap();
+    assert_eq!(edge_set(&first), edge_set(&second));
+}
+
+#[test]
+fn test_generator_descriptors_match_registry() {
+    for info in GENERATORS {
+        let generator = get_generator(info.name)
+            .unwrap_or_else(|| panic!("{} is listed but not registered", info.name));
+        // Only generators without required parameters run with none given
+        let has_required = info.params.iter().any(|param| param.required);
+        assert_eq!(generator(&HashMap::new()).is_err(), has_required, "{}", info.name);
+    }
+    let names: std::collections::HashSet<_> = GENERATORS.iter().map(|info| info.name).collect();
+    assert_eq!(names.len(), GENERATORS.len());
+}
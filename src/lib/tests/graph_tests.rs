@@ -0,0 +1,310 @@
+use graph_generation_language::types::{
+    normalize_graph, Edge, Graph, GraphFormat, MergePolicy, Node,
+};
+use graph_generation_language::GGLEngine;
+use serde_json::Value;
+
+fn graph_from_edges(edges: &[(&str, &str)]) -> Graph {
+    let mut graph = Graph::new();
+    for (i, (source, target)) in edges.iter().enumerate() {
+        graph.add_node(source.to_string(), Node::new());
+        graph.add_node(target.to_string(), Node::new());
+        graph.add_edge(
+            format!("e{i}"),
+            Edge::new(source.to_string(), target.to_string(), true),
+        );
+    }
+    graph
+}
+
+#[test]
+fn test_topological_sort_dag() {
+    let graph = graph_from_edges(&[("a", "b"), ("a", "c"), ("b", "d"), ("c", "d"), ("d", "e")]);
+    let order = graph.topological_sort().unwrap();
+    assert_eq!(order.len(), 5);
+
+    let position = |id: &str| order.iter().position(|n| n == id).unwrap();
+    for edge in graph.edges.values() {
+        assert!(position(&edge.source) < position(&edge.target));
+    }
+    // Ties are broken by node id
+    assert_eq!(order, vec!["a", "b", "c", "d", "e"]);
+}
+
+#[test]
+fn test_topological_sort_cycle() {
+    let mut graph = graph_from_edges(&[("root", "a"), ("a", "b"), ("b", "c"), ("c", "a")]);
+    graph.add_node("isolated".to_string(), Node::new());
+
+    let err = graph.topological_sort().unwrap_err();
+    assert_eq!(err.cycle.first(), err.cycle.last());
+    assert_eq!(err.cycle, vec!["a", "b", "c", "a"]);
+    assert!(err.to_string().contains("cycle"));
+}
+
+#[test]
+fn test_diff_added_node_and_changed_attribute() {
+    let mut before = graph_from_edges(&[("a", "b")]);
+    before
+        .get_node_mut("a")
+        .unwrap()
+        .metadata
+        .insert("color".to_string(), Value::from("red"));
+
+    let mut after = before.clone();
+    after.add_node("c".to_string(), Node::new());
+    after
+        .get_node_mut("a")
+        .unwrap()
+        .metadata
+        .insert("color".to_string(), Value::from("blue"));
+
+    let diff = before.diff(&after);
+    assert_eq!(diff.added_nodes, vec!["c"]);
+    assert!(diff.removed_nodes.is_empty());
+    assert!(diff.added_edges.is_empty());
+    assert!(diff.removed_edges.is_empty());
+    assert!(diff.changed_edges.is_empty());
+
+    assert_eq!(diff.changed_nodes.len(), 1);
+    let change = &diff.changed_nodes[0];
+    assert_eq!(change.id, "a");
+    assert_eq!(change.changes.len(), 1);
+    assert_eq!(change.changes[0].field, "metadata.color");
+    assert_eq!(change.changes[0].old, Some(Value::from("red")));
+    assert_eq!(change.changes[0].new, Some(Value::from("blue")));
+
+    // The reverse diff removes the node instead
+    assert_eq!(after.diff(&before).removed_nodes, vec!["c"]);
+    assert!(before.diff(&before).is_empty());
+}
+
+fn typed_graph(ids: &[&str], node_type: &str) -> Graph {
+    let mut graph = Graph::new();
+    for id in ids {
+        graph.add_node(id.to_string(), Node::new().with_type(node_type.to_string()));
+    }
+    graph
+}
+
+#[test]
+fn test_merge_overwrite() {
+    let mut graph = typed_graph(&["a", "b"], "old");
+    graph.merge(typed_graph(&["b", "c"], "new"), MergePolicy::Overwrite).unwrap();
+    assert_eq!(graph.nodes.len(), 3);
+    assert_eq!(graph.get_node("a").unwrap().r#type, "old");
+    assert_eq!(graph.get_node("b").unwrap().r#type, "new");
+}
+
+#[test]
+fn test_merge_skip() {
+    let mut graph = typed_graph(&["a", "b"], "old");
+    graph.merge(typed_graph(&["b", "c"], "new"), MergePolicy::Skip).unwrap();
+    assert_eq!(graph.nodes.len(), 3);
+    assert_eq!(graph.get_node("b").unwrap().r#type, "old");
+    assert_eq!(graph.get_node("c").unwrap().r#type, "new");
+}
+
+#[test]
+fn test_merge_error() {
+    let mut graph = typed_graph(&["a", "b"], "old");
+    let result = graph.merge(typed_graph(&["b", "c"], "new"), MergePolicy::Error);
+    assert!(result.unwrap_err().contains("\"b\""));
+    // Nothing is merged when a collision is detected
+    assert_eq!(graph.nodes.len(), 2);
+    assert!(graph.get_node("c").is_none());
+
+    graph.merge(typed_graph(&["c"], "new"), MergePolicy::Error).unwrap();
+    assert_eq!(graph.nodes.len(), 3);
+}
+
+#[test]
+fn test_engine_merge_policy() {
+    let ggl_code = r#"
+        graph test {
+            node n0 :manual;
+            generate path { nodes: 3; }
+        }
+    "#;
+
+    let mut engine = GGLEngine::new().with_merge_policy(MergePolicy::Skip);
+    engine.generate_from_ggl(ggl_code).unwrap();
+    assert_eq!(engine.get_graph().get_node("n0").unwrap().r#type, "manual");
+
+    let mut engine = GGLEngine::new().with_merge_policy(MergePolicy::Error);
+    let result = engine.generate_from_ggl(ggl_code);
+    assert!(result.unwrap_err().contains("collision"));
+}
+
+#[test]
+fn test_with_id_prefix() {
+    let graph = graph_from_edges(&[("a", "b"), ("b", "c")]).with_id_prefix("sub_");
+
+    assert_eq!(graph.nodes.len(), 3);
+    for id in ["sub_a", "sub_b", "sub_c"] {
+        assert!(graph.get_node(id).is_some());
+    }
+    assert!(graph.get_node("a").is_none());
+
+    let e0 = &graph.edges["sub_e0"];
+    assert_eq!((e0.source.as_str(), e0.target.as_str()), ("sub_a", "sub_b"));
+    let e1 = &graph.edges["sub_e1"];
+    assert_eq!((e1.source.as_str(), e1.target.as_str()), ("sub_b", "sub_c"));
+}
+
+#[test]
+fn test_edge_weight() {
+    let edge = Edge::new("a".to_string(), "b".to_string(), false)
+        .with_weight(2.5)
+        .unwrap();
+    assert_eq!(edge.weight(), Some(2.5));
+    assert_eq!(edge.metadata["weight"], 2.5);
+
+    let edge = Edge::new("a".to_string(), "b".to_string(), false);
+    assert_eq!(
+        edge.clone().with_weight(f64::NAN).unwrap_err(),
+        "Edge weight must be finite, got NaN"
+    );
+    assert_eq!(
+        edge.with_weight(f64::INFINITY).unwrap_err(),
+        "Edge weight must be finite, got inf"
+    );
+
+    let unweighted = Edge::new("a".to_string(), "b".to_string(), false);
+    assert_eq!(unweighted.weight(), None);
+
+    // Integer weights declared in GGL are read back as floats
+    let mut engine = GGLEngine::new();
+    engine
+        .generate_from_ggl("graph { node a; node b; edge e: a -- b [weight=3]; }")
+        .unwrap();
+    assert_eq!(engine.get_graph().edges["e"].weight(), Some(3.0));
+}
+
+#[test]
+fn test_contract_edge() {
+    let mut graph = graph_from_edges(&[("a", "b"), ("b", "c"), ("d", "b"), ("b", "a")]);
+    graph
+        .get_node_mut("a")
+        .unwrap()
+        .metadata
+        .insert("color".to_string(), Value::from("red"));
+    let b = graph.get_node_mut("b").unwrap();
+    b.metadata.insert("color".to_string(), Value::from("blue"));
+    b.metadata.insert("size".to_string(), Value::from(3));
+
+    graph.contract_edge("e0").unwrap();
+
+    assert_eq!(graph.nodes.len(), 3);
+    assert!(graph.get_node("b").is_none());
+    // The parallel edge b -> a is dropped instead of becoming a self-loop
+    assert_eq!(graph.edges.len(), 2);
+    assert_eq!(graph.edges["e1"].source, "a");
+    assert_eq!(graph.edges["e1"].target, "c");
+    assert_eq!(graph.edges["e2"].source, "d");
+    assert_eq!(graph.edges["e2"].target, "a");
+
+    let a = graph.get_node("a").unwrap();
+    assert_eq!(a.metadata["color"], "red");
+    assert_eq!(a.metadata["size"], 3);
+
+    assert!(graph.contract_edge("missing").is_err());
+}
+
+#[test]
+fn test_contract_edge_with_missing_endpoint_leaves_graph_unchanged() {
+    let mut graph = graph_from_edges(&[("a", "b"), ("b", "c")]);
+    graph.remove_node("c");
+    let before = graph.to_json().unwrap();
+
+    let result = graph.contract_edge("e1");
+
+    assert_eq!(result.unwrap_err(), "Target node 'c' not found");
+    assert_eq!(graph.to_json().unwrap(), before);
+}
+
+#[test]
+fn test_line_graph_of_path() {
+    let graph = graph_from_edges(&[("a", "b"), ("b", "c"), ("c", "d")]);
+    let line = graph.line_graph();
+
+    assert_eq!(line.nodes.len(), 3);
+    assert_eq!(line.edges.len(), 2);
+    assert!(line.edges.contains_key("e0--e1"));
+    assert!(line.edges.contains_key("e1--e2"));
+    assert_eq!(line.nodes["e1"].metadata["source"], "b");
+    assert_eq!(line.nodes["e1"].metadata["target"], "c");
+}
+
+#[test]
+fn test_keyed_and_array_formats() {
+    let mut graph = Graph::new();
+    graph.add_node("b".to_string(), Node::new().with_type("server".to_string()));
+    graph.add_node("a".to_string(), Node::new());
+    graph.add_edge(
+        "link".to_string(),
+        Edge::new("a".to_string(), "b".to_string(), true)
+            .with_weight(2.0)
+            .unwrap(),
+    );
+
+    let keyed = graph.to_value(GraphFormat::Keyed).unwrap();
+    assert_eq!(keyed["nodes"]["b"]["type"], "server");
+    assert_eq!(keyed["edges"]["link"]["source"], "a");
+    let default_json: Value = serde_json::from_str(&graph.to_json().unwrap()).unwrap();
+    assert_eq!(keyed, default_json);
+
+    let array = graph.to_value(GraphFormat::Array).unwrap();
+    let nodes = array["nodes"].as_array().unwrap();
+    assert_eq!(nodes.len(), 2);
+    assert_eq!(nodes[0]["id"], "a");
+    assert_eq!(nodes[1]["id"], "b");
+    assert_eq!(nodes[1]["type"], "server");
+    let edges = array["edges"].as_array().unwrap();
+    assert_eq!(edges[0]["id"], "link");
+    assert_eq!(edges[0]["target"], "b");
+    assert_eq!(edges[0]["metadata"]["weight"], 2.0);
+
+    let json: Value = serde_json::from_str(&graph.to_json_as(GraphFormat::Array).unwrap()).unwrap();
+    assert_eq!(json, array);
+}
+
+#[test]
+fn test_normalize_graph_formats() {
+    let keyed = serde_json::json!({
+        "nodes": {
+            "a": {"type": "default", "metadata": {}},
+            "b": {"type": "server", "metadata": {"cpu": 4}}
+        },
+        "edges": {
+            "e0": {"source": "a", "target": "b", "directed": false, "metadata": {}}
+        }
+    });
+    let array = serde_json::json!({
+        "nodes": [
+            {"id": "a"},
+            {"id": "b", "type": "server", "metadata": {"cpu": 4}}
+        ],
+        "edges": [
+            {"id": "e0", "source": "a", "target": "b"}
+        ]
+    });
+
+    assert_eq!(normalize_graph(keyed.clone()), keyed);
+    assert_eq!(normalize_graph(array), keyed);
+
+    let mut graph = Graph::new();
+    graph.add_node("a".to_string(), Node::new());
+    graph.add_edge(
+        "e0".to_string(),
+        Edge::new("a".to_string(), "a".to_string(), true),
+    );
+    assert_eq!(
+        normalize_graph(graph.to_value(GraphFormat::Array).unwrap()),
+        graph.to_value(GraphFormat::Keyed).unwrap()
+    );
+
+    let unnamed = normalize_graph(serde_json::json!({"edges": [{"source": "x", "target": "y"}]}));
+    assert_eq!(unnamed["edges"]["0"]["source"], "x");
+    assert_eq!(unnamed["nodes"], serde_json::json!({}));
+}
@@ -1,4 +1,4 @@
-use graph_generation_language::GGLEngine;
+use graph_generation_language::{FloatTolerance, GGLEngine};
 use serde_json::Value;
 
 #[cfg(test)]
@@ -134,6 +134,192 @@ mod basic_integration_tests {
         assert_eq!(graph["nodes"].as_object().unwrap().len(), 0);
         assert_eq!(graph["edges"].as_object().unwrap().len(), 0);
     }
+
+    #[test]
+    fn test_build_graph_without_json() {
+        let mut engine = GGLEngine::new();
+
+        let graph = engine
+            .build_graph(
+                r#"
+                graph direct {
+                    generate star {
+                        nodes: 5;
+                    }
+                    node extra :leaf [weight=2];
+                }
+            "#,
+            )
+            .unwrap();
+
+        assert_eq!(graph.node_count(), 6);
+        assert_eq!(graph.edge_count(), 4);
+        assert_eq!(graph.get_node("extra").unwrap().r#type, "leaf");
+
+        let result = engine.build_graph("graph broken {");
+        assert!(result.unwrap_err().contains("Parse error"));
+    }
+
+    #[test]
+    fn test_apply_ggl_appends_to_existing_graph() {
+        let mut engine = GGLEngine::new();
+
+        engine
+            .apply_ggl(
+                r#"
+                graph first {
+                    let prefix = "server";
+                    node a;
+                    node b;
+                    rule mark {
+                        lhs { node N; }
+                        rhs { node N [marked=true]; }
+                    }
+                }
+            "#,
+            )
+            .unwrap();
+        let graph = engine
+            .apply_ggl(
+                r#"
+                graph second {
+                    node "{prefix}_c";
+                    edge link: a -> b;
+                    edge a -- server_c;
+                    apply mark 1 times;
+                }
+            "#,
+            )
+            .unwrap();
+
+        assert_eq!(graph.node_count(), 3);
+        assert_eq!(graph.edge_count(), 2);
+        assert_eq!(graph.edges["link"].target, "b");
+        assert!(graph
+            .nodes
+            .values()
+            .any(|node| node.metadata.get("marked") == Some(&Value::Bool(true))));
+
+        engine.reset();
+        assert_eq!(engine.get_graph().node_count(), 0);
+    }
+
+    #[test]
+    fn test_apply_ggl_is_reproducible_with_seed() {
+        let ggl_code = r#"
+            graph random {
+                generate barabasi_albert { nodes: 20; edges_per_node: 2; }
+                node order [items=shuffle([1, 2, 3, 4, 5, 6, 7, 8]), pick=sample([1, 2, 3, 4, 5, 6], 3)];
+            }
+        "#;
+        // No reset() between with_seed and apply_ggl
+        let run = || {
+            let mut engine = GGLEngine::new().with_seed(7);
+            let graph = engine.apply_ggl(ggl_code).unwrap();
+            graph.to_json().unwrap()
+        };
+        assert_eq!(run(), run());
+    }
+
+    #[test]
+    fn test_engine_reuse_and_reset() {
+        let mut engine = GGLEngine::new().with_seed(3);
+        let ggl_code = r#"
+            graph reused {
+                let order = shuffle([1, 2, 3, 4, 5]);
+                node a [order=order];
+            }
+        "#;
+
+        let first = engine.generate_from_ggl(ggl_code).unwrap();
+        assert_eq!(engine.generate_from_ggl(ggl_code).unwrap(), first);
+        assert_eq!(engine.graph.nodes.len(), 1);
+
+        engine.reset();
+        assert!(engine.graph.nodes.is_empty());
+        let result = engine.generate_from_ggl("graph g { node b [order=order]; }");
+        let graph: Value = serde_json::from_str(&result.unwrap()).unwrap();
+        assert!(graph["nodes"]["a"].is_null());
+        assert_eq!(graph["nodes"]["b"]["metadata"]["order"], "order");
+    }
+
+    #[test]
+    fn test_for_loop_over_list() {
+        let mut engine = GGLEngine::new();
+
+        let ggl_code = r#"
+            graph services {
+                let extra = ["cache"];
+                for name in ["web", "api", "db"] {
+                    node "{name}" :service [label=name];
+                }
+                for name in extra {
+                    node "{name}";
+                }
+                for i in 0..2 {
+                    node "worker{i}";
+                }
+            }
+        "#;
+
+        let json_str = engine.generate_from_ggl(ggl_code).unwrap();
+        let graph: Value = serde_json::from_str(&json_str).unwrap();
+
+        let nodes = graph["nodes"].as_object().unwrap();
+        assert_eq!(nodes.len(), 6);
+        assert_eq!(nodes["api"]["type"], "service");
+        assert_eq!(nodes["api"]["metadata"]["label"], "api");
+        assert!(nodes.contains_key("cache"));
+        assert!(nodes.contains_key("worker1"));
+    }
+
+    #[test]
+    fn test_for_loop_over_non_array() {
+        let mut engine = GGLEngine::new();
+
+        let ggl_code = r#"
+            graph bad {
+                for name in "web" {
+                    node "{name}";
+                }
+            }
+        "#;
+
+        let result = engine.generate_from_ggl(ggl_code);
+        assert!(result.unwrap_err().contains("range or an array"));
+    }
+
+    #[test]
+    fn test_while_loop_grows_to_target() {
+        let mut engine = GGLEngine::new();
+
+        let ggl_code = r#"
+            graph growing {
+                node n0;
+                while node_count < 10 {
+                    let i = node_count;
+                    node "n{i}";
+                    edge: "n{i - 1}" -> "n{i}";
+                }
+            }
+        "#;
+
+        let json_str = engine.generate_from_ggl(ggl_code).unwrap();
+        let graph: Value = serde_json::from_str(&json_str).unwrap();
+        assert_eq!(graph["nodes"].as_object().unwrap().len(), 10);
+        assert_eq!(graph["edges"].as_object().unwrap().len(), 9);
+    }
+
+    #[test]
+    fn test_while_loop_iteration_cap() {
+        let mut engine = GGLEngine::new();
+
+        let result = engine.generate_from_ggl("graph g { while 1 == 1.0 { } }");
+        assert!(result.unwrap_err().contains("While loop exceeded"));
+
+        let result = engine.generate_from_ggl("graph g { while node_count { } }");
+        assert!(result.unwrap_err().contains("must be a boolean"));
+    }
 }
 
 #[cfg(test)]
@@ -235,6 +421,32 @@ mod generator_integration_tests {
         assert!(nodes.contains_key("cell2_2"));
     }
 
+    #[test]
+    fn test_generator_params_from_variables() {
+        let mut engine = GGLEngine::new();
+
+        let ggl_code = r#"
+            graph test {
+                let scale = 2;
+                let name = "cell";
+                generate grid {
+                    rows: scale * 2;
+                    cols: scale + 1;
+                    prefix: "{name}_";
+                }
+            }
+        "#;
+
+        let json_str = engine.generate_from_ggl(ggl_code).unwrap();
+        let graph: Value = serde_json::from_str(&json_str).unwrap();
+
+        // 4x3 grid
+        let nodes = graph["nodes"].as_object().unwrap();
+        assert_eq!(nodes.len(), 12);
+        assert!(nodes.contains_key("cell_3_2"));
+        assert_eq!(graph["edges"].as_object().unwrap().len(), 17);
+    }
+
     #[test]
     fn test_star_graph_generation() {
         let mut engine = GGLEngine::new();
@@ -379,126 +591,1010 @@ mod mixed_content_tests {
         let result = engine.generate_from_ggl(ggl_code);
         assert!(result.is_ok());
 
-        let json_str = result.unwrap();
+        let json_str = result.unwrap();
+        let graph: Value = serde_json::from_str(&json_str).unwrap();
+
+        let nodes = graph["nodes"].as_object().unwrap();
+        let edges = graph["edges"].as_object().unwrap();
+
+        // Should have manual nodes + generated nodes
+        assert!(nodes.len() >= 5); // 2 manual + 3 generated
+
+        // Should have manual edge + generated edges
+        assert!(edges.len() >= 4); // 1 manual + 3 generated (complete graph)
+
+        // Verify manual content exists
+        assert!(nodes.contains_key("alice"));
+        assert!(nodes.contains_key("bob"));
+        assert!(edges.contains_key("friendship"));
+
+        // Verify generated content exists
+        assert!(nodes.contains_key("user0"));
+        assert!(nodes.contains_key("user1"));
+        assert!(nodes.contains_key("user2"));
+    }
+
+    #[test]
+    fn test_multiple_generators() {
+        let mut engine = GGLEngine::new();
+
+        let ggl_code = r#"
+            graph multi_gen {
+                generate path {
+                    nodes: 3;
+                    prefix: "path";
+                }
+
+                generate star {
+                    nodes: 4;
+                    prefix: "star";
+                }
+            }
+        "#;
+
+        let result = engine.generate_from_ggl(ggl_code);
+        assert!(result.is_ok());
+
+        let json_str = result.unwrap();
+        let graph: Value = serde_json::from_str(&json_str).unwrap();
+
+        let nodes = graph["nodes"].as_object().unwrap();
+
+        // Should have nodes from both generators
+        assert_eq!(nodes.len(), 7); // 3 from path + 4 from star
+
+        // Verify path nodes
+        assert!(nodes.contains_key("path0"));
+        assert!(nodes.contains_key("path1"));
+        assert!(nodes.contains_key("path2"));
+
+        // Verify star nodes
+        assert!(nodes.contains_key("star0"));
+        assert!(nodes.contains_key("star1"));
+        assert!(nodes.contains_key("star2"));
+        assert!(nodes.contains_key("star3"));
+    }
+
+    #[test]
+    fn test_complex_attributes() {
+        let mut engine = GGLEngine::new();
+
+        let ggl_code = r#"
+            graph complex {
+                node person [
+                    name="John Doe",
+                    age=35,
+                    active=true,
+                    score=98.5,
+                    status="verified"
+                ];
+
+                node company [
+                    name="Tech Corp",
+                    employees=1000,
+                    public=false,
+                    revenue=50000000.0
+                ];
+
+                edge employment: person -> company [
+                    role="Software Engineer",
+                    salary=120000,
+                    remote=true,
+                    start_date="2023-01-15"
+                ];
+            }
+        "#;
+
+        let result = engine.generate_from_ggl(ggl_code);
+        assert!(result.is_ok());
+
+        let json_str = result.unwrap();
+        let graph: Value = serde_json::from_str(&json_str).unwrap();
+
+        // Verify complex node attributes
+        let person = &graph["nodes"]["person"]["metadata"];
+        assert_eq!(person["name"], "John Doe");
+        assert_eq!(person["age"], 35);
+        assert_eq!(person["active"], true);
+        assert_eq!(person["score"], 98.5);
+        assert_eq!(person["status"], "verified");
+
+        let company = &graph["nodes"]["company"]["metadata"];
+        assert_eq!(company["name"], "Tech Corp");
+        assert_eq!(company["employees"], 1000);
+        assert_eq!(company["public"], false);
+        assert_eq!(company["revenue"], 50000000.0);
+
+        // Verify complex edge attributes
+        let employment = &graph["edges"]["employment"]["metadata"];
+        assert_eq!(employment["role"], "Software Engineer");
+        assert_eq!(employment["salary"], 120000);
+        assert_eq!(employment["remote"], true);
+        assert_eq!(employment["start_date"], "2023-01-15");
+    }
+
+    #[test]
+    fn test_structured_attributes_round_trip() {
+        let mut engine = GGLEngine::new();
+
+        let ggl_code = r#"
+            graph structured {
+                let label = "tagged";
+                node item [tags=["a", "b"], info={label: label, sizes: [1, 2]}];
+                generate path {
+                    nodes: 2;
+                    prefix: "p";
+                    node_attrs: {kind: "cell"};
+                }
+            }
+        "#;
+
+        let json_str = engine.generate_from_ggl(ggl_code).unwrap();
+
+        // Structured metadata survives a full JSON round trip
+        let graph: graph_generation_language::types::Graph =
+            serde_json::from_str(&json_str).unwrap();
+        let item = graph.get_node("item").unwrap();
+        assert_eq!(item.metadata["tags"], serde_json::json!(["a", "b"]));
+        assert_eq!(
+            item.metadata["info"],
+            serde_json::json!({"label": "tagged", "sizes": [1, 2]})
+        );
+        assert_eq!(graph.get_node("p0").unwrap().metadata["kind"], "cell");
+    }
+
+    #[test]
+    fn test_object_shorthand_resolves_variable() {
+        let mut engine = GGLEngine::new();
+
+        let ggl_code = r#"
+            graph shorthand {
+                let id = "a";
+                node item [ref={id}];
+            }
+        "#;
+
+        let json_str = engine.generate_from_ggl(ggl_code).unwrap();
+        let graph: Value = serde_json::from_str(&json_str).unwrap();
+        assert_eq!(graph["nodes"]["item"]["metadata"]["ref"], serde_json::json!({"id": "a"}));
+    }
+
+    #[test]
+    fn test_doc_comment_metadata() {
+        let mut engine = GGLEngine::new();
+
+        let ggl_code = r#"
+            graph documented {
+                # The main database.
+                node db :database [replicas=3];
+                # Overridden below
+                node cache [doc="explicit"];
+            }
+        "#;
+
+        let json_str = engine.generate_from_ggl(ggl_code).unwrap();
+        let graph: Value = serde_json::from_str(&json_str).unwrap();
+        assert_eq!(graph["nodes"]["db"]["metadata"]["doc"], "The main database.");
+        assert_eq!(graph["nodes"]["db"]["metadata"]["replicas"], 3);
+        assert_eq!(graph["nodes"]["cache"]["metadata"]["doc"], "explicit");
+    }
+
+    #[test]
+    fn test_computed_attributes() {
+        let mut engine = GGLEngine::new();
+
+        let ggl_code = r#"
+            graph computed {
+                let scale = 3;
+                let base = 1.5;
+                node item [size=scale * 2 + base, half=scale / 2, rest=7 % scale, label="n" + scale];
+            }
+        "#;
+
+        let json_str = engine.generate_from_ggl(ggl_code).unwrap();
+        let graph: Value = serde_json::from_str(&json_str).unwrap();
+        let metadata = &graph["nodes"]["item"]["metadata"];
+        assert_eq!(metadata["size"], 7.5);
+        assert_eq!(metadata["half"], 1.5);
+        assert_eq!(metadata["rest"], 1);
+        assert_eq!(metadata["label"], "n3");
+    }
+
+    #[test]
+    fn test_interpolated_attribute_values() {
+        let mut engine = GGLEngine::new();
+
+        let ggl_code = r#"
+            graph labelled {
+                let kind = "server";
+                for i in 0..3 {
+                    node "n{i}" [label="Item {i} of {kind}", next="n{i + 1}"];
+                }
+            }
+        "#;
+
+        let json_str = engine.generate_from_ggl(ggl_code).unwrap();
+        let graph: Value = serde_json::from_str(&json_str).unwrap();
+        let metadata = &graph["nodes"]["n2"]["metadata"];
+        assert_eq!(metadata["label"], "Item 2 of server");
+        assert_eq!(metadata["next"], "n3");
+    }
+
+    #[test]
+    fn test_float_precision() {
+        let ggl_code = r#"
+            graph rounded {
+                node n [total=sum([0.1, 0.2]), third=1 / 3, count=12345, nested=[0.1 + 0.2]];
+            }
+        "#;
+
+        let json_str = GGLEngine::new().generate_from_ggl(ggl_code).unwrap();
+        let graph: Value = serde_json::from_str(&json_str).unwrap();
+        assert_eq!(graph["nodes"]["n"]["metadata"]["total"], 0.30000000000000004);
+
+        let json_str = GGLEngine::new()
+            .with_float_precision(4)
+            .generate_from_ggl(ggl_code)
+            .unwrap();
+        let graph: Value = serde_json::from_str(&json_str).unwrap();
+        let metadata = &graph["nodes"]["n"]["metadata"];
+        assert_eq!(metadata["total"], 0.3);
+        assert_eq!(metadata["third"], 0.3333);
+        assert_eq!(metadata["count"], 12345);
+        assert_eq!(metadata["nested"][0], 0.3);
+    }
+
+    #[test]
+    fn test_float_tolerance() {
+        let ggl_code = r#"
+            graph compared {
+                let total = 0.1 + 0.2;
+                node n [equal=total == 0.3, different=total != 0.3, far=total == 0.31];
+            }
+        "#;
+        let flags = |engine: GGLEngine| {
+            let mut engine = engine;
+            let json_str = engine.generate_from_ggl(ggl_code).unwrap();
+            let graph: Value = serde_json::from_str(&json_str).unwrap();
+            graph["nodes"]["n"]["metadata"].clone()
+        };
+
+        let exact = flags(GGLEngine::new());
+        assert_eq!(exact["equal"], false);
+        assert_eq!(exact["different"], true);
+
+        let relative = flags(GGLEngine::new().with_float_tolerance(FloatTolerance {
+            absolute: 0.0,
+            relative: 1e-9,
+        }));
+        assert_eq!(relative["equal"], true);
+        assert_eq!(relative["different"], false);
+        assert_eq!(relative["far"], false);
+
+        let absolute = flags(GGLEngine::new().with_float_tolerance(FloatTolerance {
+            absolute: 0.05,
+            relative: 0.0,
+        }));
+        assert_eq!(absolute["far"], true);
+    }
+
+    #[test]
+    fn test_float_tolerance_ignores_integers() {
+        let mut engine = GGLEngine::new().with_float_tolerance(FloatTolerance {
+            absolute: 0.5,
+            relative: 1e-9,
+        });
+
+        // 2^53 + 1 and 2^53 are the same f64
+        let ggl_code = r#"
+            graph compared {
+                node n [eq=9007199254740993 == 9007199254740992, ne=9007199254740993 != 9007199254740992, near=1 == 1.4];
+            }
+        "#;
+
+        let json_str = engine.generate_from_ggl(ggl_code).unwrap();
+        let graph: Value = serde_json::from_str(&json_str).unwrap();
+        let metadata = &graph["nodes"]["n"]["metadata"];
+        assert_eq!(metadata["eq"], false);
+        assert_eq!(metadata["ne"], true);
+        assert_eq!(metadata["near"], true);
+    }
+
+    #[test]
+    fn test_null_coalescing() {
+        let mut engine = GGLEngine::new();
+
+        let ggl_code = r#"
+            graph coalesced {
+                let missing = null;
+                let weight = 2.5;
+                node a [
+                    fallback=missing ?? 1.0,
+                    kept=weight ?? 1.0,
+                    falsy=false ?? true,
+                    chained=missing ?? null ?? "last",
+                    loose=missing ?? 1 + 2,
+                    raw=missing
+                ];
+            }
+        "#;
+
+        let json_str = engine.generate_from_ggl(ggl_code).unwrap();
+        let graph: Value = serde_json::from_str(&json_str).unwrap();
+        let metadata = &graph["nodes"]["a"]["metadata"];
+        assert_eq!(metadata["fallback"], 1.0);
+        assert_eq!(metadata["kept"], 2.5);
+        assert_eq!(metadata["falsy"], false);
+        assert_eq!(metadata["chained"], "last");
+        assert_eq!(metadata["loose"], 3);
+        assert_eq!(metadata["raw"], Value::Null);
+
+        // The right side is only evaluated when it is needed
+        let json_str = engine
+            .generate_from_ggl("graph g { node a [x=1 ?? nope(1)]; }")
+            .unwrap();
+        let graph: Value = serde_json::from_str(&json_str).unwrap();
+        assert_eq!(graph["nodes"]["a"]["metadata"]["x"], 1);
+        let result = engine.generate_from_ggl("graph g { node a [x=null ?? nope(1)]; }");
+        assert!(result.unwrap_err().contains("Unknown function: nope"));
+    }
+
+    #[test]
+    fn test_property_access() {
+        let mut engine = GGLEngine::new();
+
+        let ggl_code = r#"
+            graph props {
+                let config = {size: 3, meta: {weight: 2.5}};
+                let maybeNull = null;
+                node a [
+                    size=config.size * 2,
+                    nested=config.meta.weight,
+                    missing=config.missing,
+                    fallback=config.meta.missing ?? 1.0,
+                    optional=maybeNull?.field,
+                    chain=maybeNull?.field.deeper,
+                    present=config?.meta?.weight
+                ];
+            }
+        "#;
+
+        let json_str = engine.generate_from_ggl(ggl_code).unwrap();
+        let graph: Value = serde_json::from_str(&json_str).unwrap();
+        let metadata = &graph["nodes"]["a"]["metadata"];
+        assert_eq!(metadata["size"], 6);
+        assert_eq!(metadata["nested"], 2.5);
+        assert_eq!(metadata["missing"], Value::Null);
+        assert_eq!(metadata["fallback"], 1.0);
+        assert_eq!(metadata["optional"], Value::Null);
+        assert_eq!(metadata["chain"], Value::Null);
+        assert_eq!(metadata["present"], 2.5);
+
+        let result = engine.generate_from_ggl("graph g { let m = null; node a [x=m.field]; }");
+        assert!(result
+            .unwrap_err()
+            .contains("Cannot read property 'field' of null"));
+        let result = engine.generate_from_ggl("graph g { node a [x=[1, 2].length]; }");
+        assert!(result
+            .unwrap_err()
+            .contains("Cannot read property 'length' of [1,2]"));
+    }
+
+    #[test]
+    fn test_unary_operators() {
+        let mut engine = GGLEngine::new();
+
+        let ggl_code = r#"
+            graph signs {
+                let a = 2;
+                let b = 3.5;
+                let x = 4;
+                let point = {x: 1.5};
+                node n [
+                    literal=-5,
+                    negated=-(a + b),
+                    plus=+x,
+                    mirrored=-x * 2,
+                    twice=-(-x),
+                    spaced=a - -x,
+                    property=-point.x
+                ];
+            }
+        "#;
+
+        let json_str = engine.generate_from_ggl(ggl_code).unwrap();
+        let graph: Value = serde_json::from_str(&json_str).unwrap();
+        let metadata = &graph["nodes"]["n"]["metadata"];
+        assert_eq!(metadata["literal"], -5);
+        assert_eq!(metadata["negated"], -5.5);
+        assert_eq!(metadata["plus"], 4);
+        assert_eq!(metadata["mirrored"], -8);
+        assert_eq!(metadata["twice"], 4);
+        assert_eq!(metadata["spaced"], 6);
+        assert_eq!(metadata["property"], -1.5);
+
+        let result = engine.generate_from_ggl(r#"graph g { let s = "a"; node n [x=-s]; }"#);
+        assert!(result.unwrap_err().contains("Cannot apply unary '-' to \"a\""));
+        let result = engine.generate_from_ggl("graph g { let t = true; node n [x=+t]; }");
+        assert!(result.unwrap_err().contains("Cannot apply unary '+' to true"));
+    }
+
+    #[test]
+    fn test_array_and_object_comparisons() {
+        let mut engine = GGLEngine::new();
+
+        let ggl_code = r#"
+            graph compared {
+                node arrays [lt=[1, 2] < [1, 3], prefix=[1, 2] < [1, 2, 0], ge=[2] >= [1, 9], eq=[1, [2]] == [1, [2]]];
+                node objects [eq={a: 1, b: [2]} == {b: [2], a: 1}, ne={a: 1} != {a: 2}];
+            }
+        "#;
+
+        let json_str = engine.generate_from_ggl(ggl_code).unwrap();
+        let graph: Value = serde_json::from_str(&json_str).unwrap();
+        let arrays = &graph["nodes"]["arrays"]["metadata"];
+        assert_eq!(arrays["lt"], true);
+        assert_eq!(arrays["prefix"], true);
+        assert_eq!(arrays["ge"], true);
+        assert_eq!(arrays["eq"], true);
+        let objects = &graph["nodes"]["objects"]["metadata"];
+        assert_eq!(objects["eq"], true);
+        assert_eq!(objects["ne"], true);
+
+        let result = engine.generate_from_ggl("graph g { node a [x={a: 1} < {a: 2}]; }");
+        assert!(result.unwrap_err().contains("Cannot apply '<'"));
+        let result = engine.generate_from_ggl(r#"graph g { node a [x=[1] < ["a"]]; }"#);
+        assert!(result.unwrap_err().contains("Cannot apply '<'"));
+    }
+
+    #[test]
+    fn test_large_integer_comparisons_are_exact() {
+        let mut engine = GGLEngine::new();
+
+        // 2^53 + 1 rounds to 2^53 as an f64
+        let ggl_code = r#"
+            graph compared {
+                node big [gt=9007199254740993 > 9007199254740992, le=9007199254740993 <= 9007199254740992, arrays=[9007199254740992] < [9007199254740993]];
+            }
+        "#;
+
+        let json_str = engine.generate_from_ggl(ggl_code).unwrap();
+        let graph: Value = serde_json::from_str(&json_str).unwrap();
+        let big = &graph["nodes"]["big"]["metadata"];
+        assert_eq!(big["gt"], true);
+        assert_eq!(big["le"], false);
+        assert_eq!(big["arrays"], true);
+    }
+
+    #[test]
+    fn test_arithmetic_errors() {
+        let mut engine = GGLEngine::new();
+
+        let result = engine.generate_from_ggl("graph g { node a [x=1 / 0]; }");
+        assert!(result.unwrap_err().contains("Division by zero"));
+
+        let result = engine.generate_from_ggl("graph g { node a [x=[1] * 2]; }");
+        assert!(result.unwrap_err().contains("Cannot apply '*'"));
+
+        let result = engine.generate_from_ggl("graph g { node a [x=true < false]; }");
+        assert!(result
+            .unwrap_err()
+            .contains("Booleans only support '==' and '!=', got true < false"));
+        let json_str = engine
+            .generate_from_ggl("graph g { node a [x=true != false]; }")
+            .unwrap();
+        let graph: Value = serde_json::from_str(&json_str).unwrap();
+        assert_eq!(graph["nodes"]["a"]["metadata"]["x"], true);
+    }
+
+    #[test]
+    fn test_seeded_shuffle() {
+        let ggl_code = r#"
+            graph shuffled {
+                let items = [1, 2, 3, 4, 5, 6, 7, 8];
+                node n [order=shuffle(items)];
+            }
+        "#;
+        let order = |seed| {
+            let json_str = GGLEngine::new()
+                .with_seed(seed)
+                .generate_from_ggl(ggl_code)
+                .unwrap();
+            let graph: Value = serde_json::from_str(&json_str).unwrap();
+            graph["nodes"]["n"]["metadata"]["order"].clone()
+        };
+
+        let first = order(42);
+        assert_eq!(first, order(42));
+
+        let mut elements: Vec<i64> = first
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v.as_i64().unwrap())
+            .collect();
+        elements.sort();
+        assert_eq!(elements, vec![1, 2, 3, 4, 5, 6, 7, 8]);
+
+        let mut engine = GGLEngine::new();
+        let result = engine.generate_from_ggl("graph g { node a [x=shuffle(1)]; }");
+        assert!(result.unwrap_err().contains("shuffle expects an array"));
+        let result = engine.generate_from_ggl("graph g { node a [x=nope([1])]; }");
+        assert!(result.unwrap_err().contains("Unknown function: nope"));
+    }
+
+    #[test]
+    fn test_seeded_sample() {
+        let ggl_code = r#"
+            graph sampled {
+                node n [picked=sample([1, 2, 3, 4, 5, 6, 7, 8], 3)];
+            }
+        "#;
+        let picked = |seed| {
+            let json_str = GGLEngine::new()
+                .with_seed(seed)
+                .generate_from_ggl(ggl_code)
+                .unwrap();
+            let graph: Value = serde_json::from_str(&json_str).unwrap();
+            graph["nodes"]["n"]["metadata"]["picked"].clone()
+        };
+
+        let first = picked(7);
+        assert_eq!(first, picked(7));
+
+        let mut elements: Vec<i64> = first
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v.as_i64().unwrap())
+            .collect();
+        assert_eq!(elements.len(), 3);
+        elements.sort();
+        elements.dedup();
+        assert_eq!(elements.len(), 3);
+        assert!(elements.iter().all(|e| (1..=8).contains(e)));
+
+        let mut engine = GGLEngine::new();
+        let result = engine.generate_from_ggl("graph g { node a [x=sample([1, 2], 3)]; }");
+        assert!(result
+            .unwrap_err()
+            .contains("sample size 3 exceeds array length 2"));
+    }
+
+    #[test]
+    fn test_trace_records_call_chain() {
+        let ggl_code = r#"
+            graph traced {
+                let total = sum(sample(shuffle([1, 2, 3, 4]), 2));
+                node n [size=clamp(total, 0, 100)];
+            }
+        "#;
+        let mut engine = GGLEngine::new().with_seed(3).with_trace(true);
+        engine.build_graph(ggl_code).unwrap();
+        let calls: Vec<&str> = engine
+            .trace()
+            .iter()
+            .map(|line| line.split('(').next().unwrap())
+            .collect();
+        assert_eq!(calls, ["shuffle", "sample", "sum", "clamp"]);
+        assert_eq!(engine.trace()[0], "shuffle([4 items]) -> [4 items]");
+        assert_eq!(engine.trace()[1], "sample([4 items], 2) -> [2 items]");
+
+        // Each run starts a fresh trace, and nothing is recorded when disabled
+        engine.build_graph(ggl_code).unwrap();
+        assert_eq!(engine.trace().len(), 4);
+        let mut engine = GGLEngine::new();
+        engine.build_graph(ggl_code).unwrap();
+        assert!(engine.trace().is_empty());
+    }
+
+    #[test]
+    fn test_numeric_reducers() {
+        let mut engine = GGLEngine::new();
+
+        let ggl_code = r#"
+            graph reduced {
+                let values = [1, 2, 3, 4];
+                node stats [total=sum(values), prod=product(values), mean=average(values), mixed=sum([1, 0.5])];
+                node empty [total=sum([]), prod=product([])];
+            }
+        "#;
+
+        let json_str = engine.generate_from_ggl(ggl_code).unwrap();
+        let graph: Value = serde_json::from_str(&json_str).unwrap();
+        let stats = &graph["nodes"]["stats"]["metadata"];
+        assert_eq!(stats["total"], 10);
+        assert_eq!(stats["prod"], 24);
+        assert_eq!(stats["mean"], 2.5);
+        assert_eq!(stats["mixed"], 1.5);
+        let empty = &graph["nodes"]["empty"]["metadata"];
+        assert_eq!(empty["total"], 0);
+        assert_eq!(empty["prod"], 1);
+
+        let result = engine.generate_from_ggl("graph g { node a [x=average([])]; }");
+        assert!(result.unwrap_err().contains("average of an empty array"));
+        let result = engine.generate_from_ggl(r#"graph g { node a [x=sum([1, "two"])]; }"#);
+        assert!(result
+            .unwrap_err()
+            .contains("sum expects numeric elements"));
+    }
+
+    #[test]
+    fn test_clamp_and_lerp() {
+        let mut engine = GGLEngine::new();
+
+        let ggl_code = r#"
+            graph math {
+                node c [below=clamp(-5, 0, 10), within=clamp(4, 0, 10), above=clamp(12.5, 0, 10)];
+                node l [start=lerp(10, 20, 0), middle=lerp(10, 20, 0.5), end=lerp(10, 20, 1)];
+            }
+        "#;
+
+        let json_str = engine.generate_from_ggl(ggl_code).unwrap();
+        let graph: Value = serde_json::from_str(&json_str).unwrap();
+        let clamped = &graph["nodes"]["c"]["metadata"];
+        assert_eq!(clamped["below"], 0);
+        assert_eq!(clamped["within"], 4);
+        assert_eq!(clamped["above"], 10);
+        let lerped = &graph["nodes"]["l"]["metadata"];
+        assert_eq!(lerped["start"], 10.0);
+        assert_eq!(lerped["middle"], 15.0);
+        assert_eq!(lerped["end"], 20.0);
+
+        let result = engine.generate_from_ggl("graph g { node a [x=clamp(1, 5, 0)]; }");
+        assert!(result.unwrap_err().contains("clamp bounds are reversed"));
+        let result = engine.generate_from_ggl("graph g { node a [x=lerp(1, 2)]; }");
+        assert!(result.unwrap_err().contains("lerp expects 3 argument(s), got 2"));
+    }
+
+    #[test]
+    fn test_sort_keys_is_insertion_order_independent() {
+        let mut engine = GGLEngine::new();
+
+        let ggl_code = r#"
+            graph sorted {
+                node a [value=sortKeys({zeta: 1, alpha: 2, mid: {y: 1, x: 2}})];
+                node b [value=sortKeys({mid: {x: 2, y: 1}, alpha: 2, zeta: 1})];
+            }
+        "#;
+
+        let json_str = engine.generate_from_ggl(ggl_code).unwrap();
+        let graph: Value = serde_json::from_str(&json_str).unwrap();
+        let serialized =
+            |id: &str| serde_json::to_string(&graph["nodes"][id]["metadata"]["value"]).unwrap();
+        assert_eq!(serialized("a"), serialized("b"));
+        assert_eq!(
+            serialized("a"),
+            r#"{"alpha":2,"mid":{"x":2,"y":1},"zeta":1}"#
+        );
+    }
+
+    #[test]
+    fn test_get_with_default() {
+        let mut engine = GGLEngine::new();
+
+        let ggl_code = r#"
+            graph defaults {
+                let item = {weight: 2, label: null};
+                node a [
+                    present=get(item, "weight", 1),
+                    missing=get(item, "color", "gray"),
+                    null_valued=get(item, "label", "none"),
+                    null_object=get(null, "weight", 0)
+                ];
+            }
+        "#;
+
+        let json_str = engine.generate_from_ggl(ggl_code).unwrap();
+        let graph: Value = serde_json::from_str(&json_str).unwrap();
+        let metadata = &graph["nodes"]["a"]["metadata"];
+        assert_eq!(metadata["present"], 2);
+        assert_eq!(metadata["missing"], "gray");
+        assert_eq!(metadata["null_valued"], "none");
+        assert_eq!(metadata["null_object"], 0);
+
+        let result = engine.generate_from_ggl(r#"graph g { node a [x=get([1], "k", 0)]; }"#);
+        assert!(result.unwrap_err().contains("get expects an object, got [1]"));
+        let result = engine.generate_from_ggl("graph g { node a [x=get({a: 1}, 1, 0)]; }");
+        assert!(result.unwrap_err().contains("get expects a string key, got 1"));
+        let result = engine.generate_from_ggl(r#"graph g { node a [x=get({a: 1}, "a")]; }"#);
+        assert!(result.unwrap_err().contains("get expects 3 argument(s), got 2"));
+    }
+
+    #[test]
+    fn test_assert() {
+        let mut engine = GGLEngine::new();
+
+        let ggl_code = r#"
+            graph checked {
+                let sizes = [1, 2, 3];
+                node a [ok=assert(sum(sizes) == 6, "sizes must add up to 6")];
+            }
+        "#;
+        let json_str = engine.generate_from_ggl(ggl_code).unwrap();
+        let graph: Value = serde_json::from_str(&json_str).unwrap();
+        assert_eq!(graph["nodes"]["a"]["metadata"]["ok"], true);
+
+        let ggl_code = r#"
+            graph checked {
+                for i in 0..3 {
+                    node "n{i}" [ok=assert(i < 2, "too many nodes: {i}")];
+                }
+            }
+        "#;
+        let err = engine.generate_from_ggl(ggl_code).unwrap_err();
+        assert!(err.contains("Assertion failed: too many nodes: 2"));
+        let result = engine.generate_from_ggl(r#"graph g { node a [x=assert(1, "m")]; }"#);
+        assert!(result.unwrap_err().contains("assert expects a boolean condition, got 1"));
+    }
+
+    #[test]
+    fn test_len() {
+        let mut engine = GGLEngine::new();
+
+        let ggl_code = r#"
+            graph sized {
+                node a [
+                    array=len([1, [2, 3], 4]),
+                    empty=len([]),
+                    string=len("héllo"),
+                    object=len({a: 1, b: {c: 2}})
+                ];
+            }
+        "#;
+
+        let json_str = engine.generate_from_ggl(ggl_code).unwrap();
+        let graph: Value = serde_json::from_str(&json_str).unwrap();
+        let metadata = &graph["nodes"]["a"]["metadata"];
+        assert_eq!(metadata["array"], 3);
+        assert_eq!(metadata["empty"], 0);
+        assert_eq!(metadata["string"], 5);
+        assert_eq!(metadata["object"], 2);
+
+        let result = engine.generate_from_ggl("graph g { node a [x=len(3)]; }");
+        assert!(result.unwrap_err().contains("len expects an array, string or object, got 3"));
+    }
+
+    #[test]
+    fn test_from_entries() {
+        let mut engine = GGLEngine::new();
+
+        let ggl_code = r#"
+            graph built {
+                let pairs = [["size", 3], [1, "one"], ["size", 4], [true, [1, 2]]];
+                node a [
+                    value=fromEntries(pairs),
+                    matches=fromEntries([["a", 1], ["b", {c: 2}]]) == {a: 1, b: {c: 2}},
+                    empty=fromEntries([])
+                ];
+            }
+        "#;
+
+        let json_str = engine.generate_from_ggl(ggl_code).unwrap();
+        let graph: Value = serde_json::from_str(&json_str).unwrap();
+        let metadata = &graph["nodes"]["a"]["metadata"];
+        assert_eq!(
+            metadata["value"],
+            serde_json::json!({"size": 4, "1": "one", "true": [1, 2]})
+        );
+        assert_eq!(metadata["matches"], true);
+        assert_eq!(metadata["empty"], serde_json::json!({}));
+
+        let result = engine.generate_from_ggl(r#"graph g { node a [x=fromEntries([["k"]])]; }"#);
+        assert!(result
+            .unwrap_err()
+            .contains(r#"fromEntries expects [key, value] pairs, got ["k"]"#));
+        let result = engine.generate_from_ggl("graph g { node a [x=fromEntries([1])]; }");
+        assert!(result.unwrap_err().contains("fromEntries expects [key, value] pairs, got 1"));
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut engine = GGLEngine::new();
+
+        let ggl_code = r#"
+            graph merged {
+                let defaults = {color: "gray", size: 1, style: {dashed: true}};
+                node a [
+                    value=merge(defaults, {size: 2}, {color: "red", style: {width: 3}}),
+                    none=merge()
+                ];
+            }
+        "#;
+
+        let json_str = engine.generate_from_ggl(ggl_code).unwrap();
         let graph: Value = serde_json::from_str(&json_str).unwrap();
+        let metadata = &graph["nodes"]["a"]["metadata"];
+        // Later objects win, and nested objects are replaced rather than merged
+        assert_eq!(
+            metadata["value"],
+            serde_json::json!({"color": "red", "size": 2, "style": {"width": 3}})
+        );
+        assert_eq!(metadata["none"], serde_json::json!({}));
 
-        let nodes = graph["nodes"].as_object().unwrap();
-        let edges = graph["edges"].as_object().unwrap();
-
-        // Should have manual nodes + generated nodes
-        assert!(nodes.len() >= 5); // 2 manual + 3 generated
+        let result = engine.generate_from_ggl("graph g { node a [x=merge({a: 1}, [2])]; }");
+        assert!(result.unwrap_err().contains("merge expects objects, got [2]"));
+    }
 
-        // Should have manual edge + generated edges
-        assert!(edges.len() >= 4); // 1 manual + 3 generated (complete graph)
+    #[test]
+    fn test_hypot() {
+        let mut engine = GGLEngine::new();
 
-        // Verify manual content exists
-        assert!(nodes.contains_key("alice"));
-        assert!(nodes.contains_key("bob"));
-        assert!(edges.contains_key("friendship"));
+        let ggl_code = r#"
+            graph geometry {
+                let dx = 3;
+                let dy = 4;
+                node p [flat=hypot(dx, dy), space=hypot(2, 3, 6), exact=hypot(dx, dy) == 5];
+            }
+        "#;
 
-        // Verify generated content exists
-        assert!(nodes.contains_key("user0"));
-        assert!(nodes.contains_key("user1"));
-        assert!(nodes.contains_key("user2"));
+        let json_str = engine.generate_from_ggl(ggl_code).unwrap();
+        let graph: Value = serde_json::from_str(&json_str).unwrap();
+        let metadata = &graph["nodes"]["p"]["metadata"];
+        assert_eq!(metadata["flat"], 5.0);
+        assert_eq!(metadata["space"], 7.0);
+        assert_eq!(metadata["exact"], true);
     }
 
     #[test]
-    fn test_multiple_generators() {
+    fn test_proximity_edges() {
         let mut engine = GGLEngine::new();
 
         let ggl_code = r#"
-            graph multi_gen {
-                generate path {
-                    nodes: 3;
-                    prefix: "path";
+            graph nearby {
+                let points = [
+                    {id: "a", x: 0, y: 0},
+                    {id: "b", x: 1, y: 1},
+                    {id: "c", x: 2, y: 0},
+                    {id: "d", x: 10, y: 10}
+                ];
+                for p in points {
+                    node p.id [x=p.x, y=p.y];
                 }
-
-                generate star {
-                    nodes: 4;
-                    prefix: "star";
+                for e in proximity_edges(points, 1.5) {
+                    edge e.source -> e.target;
                 }
             }
         "#;
 
-        let result = engine.generate_from_ggl(ggl_code);
-        assert!(result.is_ok());
-
-        let json_str = result.unwrap();
+        let json_str = engine.generate_from_ggl(ggl_code).unwrap();
         let graph: Value = serde_json::from_str(&json_str).unwrap();
+        assert_eq!(graph["nodes"].as_object().unwrap().len(), 4);
+        let mut edges: Vec<_> = graph["edges"]
+            .as_object()
+            .unwrap()
+            .values()
+            .map(|edge| (edge["source"].as_str().unwrap(), edge["target"].as_str().unwrap()))
+            .collect();
+        edges.sort();
+        assert_eq!(edges, [("a", "b"), ("b", "c")]);
+
+        let result = engine.generate_from_ggl("graph g { node n [e=proximity_edges([{id: 1}], 2)]; }");
+        assert!(result
+            .unwrap_err()
+            .contains("proximity_edges expects nodes with id, x and y"));
+    }
 
-        let nodes = graph["nodes"].as_object().unwrap();
-
-        // Should have nodes from both generators
-        assert_eq!(nodes.len(), 7); // 3 from path + 4 from star
+    #[test]
+    fn test_grid_edges_matches_toroidal_grid() {
+        let mut engine = GGLEngine::new();
 
-        // Verify path nodes
-        assert!(nodes.contains_key("path0"));
-        assert!(nodes.contains_key("path1"));
-        assert!(nodes.contains_key("path2"));
+        let mut edges_of = |ggl_code: &str| {
+            let json_str = engine.generate_from_ggl(ggl_code).unwrap();
+            let graph: Value = serde_json::from_str(&json_str).unwrap();
+            let mut edges: Vec<_> = graph["edges"]
+                .as_object()
+                .unwrap()
+                .values()
+                .map(|edge| {
+                    (
+                        edge["source"].as_str().unwrap().to_string(),
+                        edge["target"].as_str().unwrap().to_string(),
+                    )
+                })
+                .collect();
+            edges.sort();
+            edges
+        };
+
+        let expected = edges_of(
+            r#"graph mesh { generate grid { rows: 3; cols: 4; prefix: "t"; periodic: true; } }"#,
+        );
+        let built = edges_of(
+            r#"graph mesh { for e in grid_edges(3, 4, "t", true) { edge e.source -> e.target; } }"#,
+        );
+        assert_eq!(built.len(), 24);
+        assert_eq!(built, expected);
 
-        // Verify star nodes
-        assert!(nodes.contains_key("star0"));
-        assert!(nodes.contains_key("star1"));
-        assert!(nodes.contains_key("star2"));
-        assert!(nodes.contains_key("star3"));
+        let open = edges_of(
+            r#"graph mesh { for e in grid_edges(2, 3, "m") { edge e.source -> e.target; } }"#,
+        );
+        assert_eq!(open.len(), 7);
+        assert!(open.contains(&("m0_0".to_string(), "m0_1".to_string())));
+        assert!(!open.contains(&("m0_2".to_string(), "m0_0".to_string())));
     }
 
     #[test]
-    fn test_complex_attributes() {
+    fn test_grid_edges_wraps_narrow_grids_once() {
         let mut engine = GGLEngine::new();
 
-        let ggl_code = r#"
-            graph complex {
-                node person [
-                    name="John Doe",
-                    age=35,
-                    active=true,
-                    score=98.5,
-                    status="verified"
-                ];
+        let mut edges_of = |rows: usize, cols: usize| {
+            let ggl_code =
+                format!(r#"graph g {{ node info [edges=grid_edges({rows}, {cols}, "n", true)]; }}"#);
+            let json_str = engine.generate_from_ggl(&ggl_code).unwrap();
+            let graph: Value = serde_json::from_str(&json_str).unwrap();
+            graph["nodes"]["info"]["metadata"]["edges"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|edge| {
+                    let end = |key: &str| edge[key].as_str().unwrap().to_string();
+                    format!("{}-{}", end("source"), end("target"))
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // A single row wraps around, but never onto itself
+        assert_eq!(edges_of(1, 4), ["n0_0-n0_1", "n0_1-n0_2", "n0_2-n0_3", "n0_3-n0_0"]);
+        assert_eq!(edges_of(1, 1), Vec::<String>::new());
+        // Wrapping two cells would repeat the edge between them
+        assert_eq!(edges_of(2, 2), ["n0_0-n0_1", "n0_0-n1_0", "n0_1-n1_1", "n1_0-n1_1"]);
+    }
 
-                node company [
-                    name="Tech Corp",
-                    employees=1000,
-                    public=false,
-                    revenue=50000000.0
-                ];
+    #[test]
+    fn test_match() {
+        let mut engine = GGLEngine::new();
 
-                edge employment: person -> company [
-                    role="Software Engineer",
-                    salary=120000,
-                    remote=true,
-                    start_date="2023-01-15"
-                ];
+        let ggl_code = r#"
+            graph roles {
+                for i in 0..4 {
+                    node "n{i}" [role=match (i % 3) { 0 => "hub", 1 => "spoke", _ => "leaf" }];
+                }
+                node shape [kind=match ([1, {a: 2}]) { [1] => "short", [1, {a: 2}] => "pair" }];
+                node half [kind=match (4 / 2.0) { 2 => "two", _ => "other" }];
             }
         "#;
-
-        let result = engine.generate_from_ggl(ggl_code);
-        assert!(result.is_ok());
-
-        let json_str = result.unwrap();
+        let json_str = engine.generate_from_ggl(ggl_code).unwrap();
         let graph: Value = serde_json::from_str(&json_str).unwrap();
+        let nodes = &graph["nodes"];
+        assert_eq!(nodes["n0"]["metadata"]["role"], "hub");
+        assert_eq!(nodes["n1"]["metadata"]["role"], "spoke");
+        assert_eq!(nodes["n2"]["metadata"]["role"], "leaf");
+        assert_eq!(nodes["n3"]["metadata"]["role"], "hub");
+        assert_eq!(nodes["shape"]["metadata"]["kind"], "pair");
+        assert_eq!(nodes["half"]["metadata"]["kind"], "two");
+
+        // Only the chosen arm is evaluated
+        let ggl_code = r#"graph g { node a [x=match (1) { 1 => "one", 2 => assert(false, "unreachable") }]; }"#;
+        let json_str = engine.generate_from_ggl(ggl_code).unwrap();
+        assert!(json_str.contains("\"one\""));
+
+        let result = engine.generate_from_ggl(r#"graph g { node a [x=match ("c") { "a" => 1, "b" => 2 }]; }"#);
+        assert!(result.unwrap_err().contains(r#"No match arm for "c""#));
+    }
 
-        // Verify complex node attributes
-        let person = &graph["nodes"]["person"]["metadata"];
-        assert_eq!(person["name"], "John Doe");
-        assert_eq!(person["age"], 35);
-        assert_eq!(person["active"], true);
-        assert_eq!(person["score"], 98.5);
-        assert_eq!(person["status"], "verified");
-
-        let company = &graph["nodes"]["company"]["metadata"];
-        assert_eq!(company["name"], "Tech Corp");
-        assert_eq!(company["employees"], 1000);
-        assert_eq!(company["public"], false);
-        assert_eq!(company["revenue"], 50000000.0);
+    #[test]
+    fn test_spread_arguments() {
+        let mut engine = GGLEngine::new();
 
-        // Verify complex edge attributes
-        let employment = &graph["edges"]["employment"]["metadata"];
-        assert_eq!(employment["role"], "Software Engineer");
-        assert_eq!(employment["salary"], 120000);
-        assert_eq!(employment["remote"], true);
-        assert_eq!(employment["start_date"], "2023-01-15");
+        // There are no lambdas, so spreads go into builtin calls
+        let ggl_code = r#"
+            graph spread {
+                let bounds = [10, 20];
+                node a [flat=hypot(...[3, 4]), mixed=lerp(...bounds, 0.5), space=hypot(...[], 2, ...[3, 6])];
+            }
+        "#;
+        let json_str = engine.generate_from_ggl(ggl_code).unwrap();
+        let graph: Value = serde_json::from_str(&json_str).unwrap();
+        let metadata = &graph["nodes"]["a"]["metadata"];
+        assert_eq!(metadata["flat"], 5.0);
+        assert_eq!(metadata["mixed"], 15.0);
+        assert_eq!(metadata["space"], 7.0);
+
+        let result = engine.generate_from_ggl("graph g { node a [x=lerp(...[1, 2])]; }");
+        assert!(result.unwrap_err().contains("lerp expects 3 argument(s), got 2"));
+        let result = engine.generate_from_ggl("graph g { node a [x=hypot(...3)]; }");
+        assert!(result.unwrap_err().contains("hypot can only spread an array, got 3"));
     }
 }
 
@@ -656,6 +1752,31 @@ mod rule_integration_tests {
         assert!(nodes.contains_key("root"));
     }
 
+    #[test]
+    fn test_rule_application_is_byte_identical_across_runs() {
+        let ggl_code = r#"
+            graph growing {
+                generate barabasi_albert {
+                    nodes: 40;
+                    edges_per_node: 2;
+                    seed: 11;
+                }
+                rule close_triangle {
+                    lhs { node A; node B; node C; edge A -- B; edge B -- C; }
+                    rhs { node A [closed=true, round=1]; node B; node C; edge A -- B; edge B -- C; edge C -- A; }
+                }
+                apply close_triangle 5 times;
+            }
+        "#;
+
+        let first = GGLEngine::new().generate_from_ggl(ggl_code).unwrap();
+        for _ in 0..5 {
+            assert_eq!(GGLEngine::new().generate_from_ggl(ggl_code).unwrap(), first);
+        }
+        let graph: Value = serde_json::from_str(&first).unwrap();
+        assert!(graph["edges"].as_object().unwrap().contains_key("new_edge_0"));
+    }
+
     #[test]
     fn test_rule_with_no_matches() {
         let mut engine = GGLEngine::new();
@@ -773,6 +1894,141 @@ mod error_handling_tests {
         let result = engine.generate_from_ggl(ggl_code);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_deeply_nested_literal() {
+        let mut engine = GGLEngine::new();
+        let max = graph_generation_language::parser::MAX_NESTING_DEPTH;
+
+        // Deep nesting must produce an error rather than overflow the stack
+        let nested = |open: &str, inner: &str, close: &str, depth: usize| {
+            format!(
+                "graph test {{ node n [value={}{inner}{}]; }}",
+                open.repeat(depth),
+                close.repeat(depth)
+            )
+        };
+        // Operator and accessor chains build expressions as deep as they are long
+        let chain = |term: &str, separator: &str, length: usize| {
+            let terms = vec![term; length].join(separator);
+            format!("graph test {{ let o = {{}}; node n [value={terms}]; }}")
+        };
+        for ggl_code in [
+            nested("[", "1", "]", 10_000),
+            nested("(1 + ", "1", ")", 10_000),
+            nested("\"{", "1", "}\"", 10_000),
+            chain("1", " + ", 100_000),
+            chain("o", "?.", 100_000),
+            chain("match (1) { _ => 1 }", " + ", 100_000),
+        ] {
+            let result = engine.generate_from_ggl(&ggl_code);
+            assert!(result.unwrap_err().contains("maximum depth"));
+        }
+
+        // Nesting up to the limit works on a 1 MiB stack, as in wasm. The graph body
+        // and attribute list count too.
+        let depth = max - 2;
+        let programs = [
+            nested("[", "1", "]", depth),
+            nested("{a: ", "1", "}", depth),
+            // Each level is a parenthesis and an operator
+            nested("(1 + ", "1", ")", depth / 2),
+            nested("\"{", "1", "}\"", depth),
+            chain("1", " + ", depth),
+            chain("o", "?.", depth),
+            // Each level is a brace and an arrow
+            nested("match (1) { _ => ", "1", " }", depth / 2),
+            format!(
+                "graph test {{ {} node n; {} }}",
+                "for i in 0..1 { ".repeat(max - 1),
+                "}".repeat(max - 1)
+            ),
+        ];
+        let handle = std::thread::Builder::new()
+            .stack_size(1 << 20)
+            .spawn(move || {
+                programs
+                    .iter()
+                    .map(|ggl_code| GGLEngine::new().generate_from_ggl(ggl_code))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap();
+        for result in handle.join().unwrap() {
+            assert!(result.is_ok(), "{:?}", result.err());
+        }
+
+        // Brackets in string text and comments are not nesting
+        let ggl_code = format!(
+            "graph test {{ let x = 1; node n [label=\"{}{{x}}{}\"]; /* {} */ }}",
+            "(".repeat(max),
+            "[".repeat(max),
+            "{".repeat(max)
+        );
+        let result = engine.generate_from_ggl(&ggl_code);
+        assert!(result.is_ok(), "{:?}", result.err());
+
+        // Operators only count within one expression
+        let ggl_code = format!(
+            "graph test {{ let i = 0; node n [{}]; {} }}",
+            vec!["x=1 + 1"; max].join(", "),
+            "while i < 0 { node m; } let y = -1.5 + 2; edge n -> m;".repeat(max)
+        );
+        let result = engine.generate_from_ggl(&ggl_code);
+        assert!(result.is_ok(), "{:?}", result.err());
+    }
+
+    #[test]
+    fn test_non_finite_float_literal() {
+        let mut engine = GGLEngine::new();
+
+        // A literal too large for f64 parses to infinity, which JSON cannot represent
+        let huge = format!("{}.0", "9".repeat(400));
+        let ggl_code = format!("graph test {{ node n [value={huge}]; }}");
+
+        let result = engine.generate_from_ggl(&ggl_code);
+        assert!(result.is_err());
+        assert!(result.err().unwrap().contains("Non-finite number"));
+    }
+
+    #[test]
+    fn test_undefined_identifier_lenient_by_default() {
+        let mut engine = GGLEngine::new();
+
+        let ggl_code = r#"
+            graph test {
+                node a [status=active];
+            }
+        "#;
+
+        let json_str = engine.generate_from_ggl(ggl_code).unwrap();
+        let graph: Value = serde_json::from_str(&json_str).unwrap();
+        assert_eq!(graph["nodes"]["a"]["metadata"]["status"], "active");
+    }
+
+    #[test]
+    fn test_undefined_identifier_strict() {
+        let mut engine = GGLEngine::new().with_strict_identifiers(true);
+
+        // Names stay lenient, and defined variables resolve as usual
+        let ggl_code = r#"
+            graph test {
+                let weight = 2;
+                node a :server [load=weight];
+                node b;
+                edge: a -- b;
+            }
+        "#;
+        assert!(engine.generate_from_ggl(ggl_code).is_ok());
+
+        let ggl_code = r#"
+            graph test {
+                let weight = 2;
+                node a [load=wieght];
+            }
+        "#;
+        let result = engine.generate_from_ggl(ggl_code);
+        assert_eq!(result.unwrap_err(), "undefined variable: wieght");
+    }
 }
 
 #[cfg(test)]
@@ -1012,4 +2268,157 @@ mod performance_tests {
             assert_eq!(node["metadata"]["timestamp"], 123456);
         }
     }
+
+    #[test]
+    fn test_rule_application_on_large_graph() {
+        let mut engine = GGLEngine::new();
+
+        let ggl_code = r#"
+            graph large_rules {
+                generate path {
+                    nodes: 1000;
+                    prefix: "p";
+                    node_attrs: { paired: false };
+                }
+
+                rule pair_up {
+                    lhs {
+                        node A [paired=false];
+                        node B [paired=false];
+                        edge: A -- B;
+                    }
+                    rhs {
+                        node A [paired=true];
+                        node B [paired=true];
+                        edge: A -- B;
+                    }
+                }
+
+                apply pair_up 1000 times;
+            }
+        "#;
+
+        let start = std::time::Instant::now();
+        let result = engine.generate_from_ggl(ggl_code);
+        let duration = start.elapsed();
+
+        assert!(result.is_ok(), "{:?}", result.err());
+        assert!(duration.as_secs() < 5); // Should complete within 5 seconds
+
+        let graph: Value = serde_json::from_str(&result.unwrap()).unwrap();
+        let nodes = graph["nodes"].as_object().unwrap();
+        assert_eq!(nodes.len(), 1000);
+        assert!(nodes.values().all(|node| node["metadata"]["paired"] == true));
+    }
+}
+
+#[cfg(test)]
+mod limit_tests {
+    use super::*;
+
+    #[test]
+    fn test_generator_exceeding_node_limit() {
+        let mut engine = GGLEngine::new().with_max_nodes(1000);
+
+        // Would be ~5 billion edges if the generator were allowed to run
+        let ggl_code = r#"
+            graph runaway {
+                generate complete {
+                    nodes: 100000;
+                }
+            }
+        "#;
+
+        let result = engine.generate_from_ggl(ggl_code);
+        assert!(result.is_err());
+        assert!(result.err().unwrap().contains("exceeding the limit of 1000"));
+    }
+
+    #[test]
+    fn test_generator_within_limits() {
+        let mut engine = GGLEngine::new().with_max_nodes(10).with_max_edges(45);
+
+        let result = engine.generate_from_ggl("graph { generate complete { nodes: 10; } }");
+        assert!(result.is_ok(), "{:?}", result.err());
+    }
+
+    #[test]
+    fn test_grid_edge_limit_is_exact() {
+        // A 10x10 grid has 180 edges, or 200 once it wraps around
+        let grid = |periodic: bool| {
+            format!("graph {{ generate grid {{ rows: 10; cols: 10; periodic: {periodic}; }} }}")
+        };
+
+        let mut engine = GGLEngine::new().with_max_edges(180);
+        let result = engine.generate_from_ggl(&grid(false));
+        assert!(result.is_ok(), "{:?}", result.err());
+
+        let mut engine = GGLEngine::new().with_max_edges(190);
+        let result = engine.generate_from_ggl(&grid(true));
+        assert!(result.unwrap_err().contains("would create 200 edges"));
+    }
+
+    #[test]
+    fn test_huge_barabasi_albert_hits_the_limit() {
+        // Counting the initial clique's edges overflows a usize unless it saturates
+        let ggl_code = r#"
+            graph huge {
+                generate barabasi_albert { nodes: 5000000000; edges_per_node: 1; initial_nodes: 5000000000; }
+            }
+        "#;
+
+        let mut engine = GGLEngine::new().with_max_nodes(10);
+        let result = engine.generate_from_ggl(ggl_code);
+        assert!(result.unwrap_err().contains("exceeding the limit of 10"));
+    }
+
+    #[test]
+    fn test_grid_edges_respects_edge_limit() {
+        let ggl_code = r#"graph g { for e in grid_edges(3, 4, "t", true) { edge e.source -> e.target; } }"#;
+
+        let mut engine = GGLEngine::new().with_max_edges(24);
+        let result = engine.generate_from_ggl(ggl_code);
+        assert!(result.is_ok(), "{:?}", result.err());
+
+        // Refused before any edge is listed
+        let mut engine = GGLEngine::new().with_max_edges(23);
+        let result = engine.generate_from_ggl(r#"graph g { let e = grid_edges(3, 4, "t", true); }"#);
+        assert!(result.unwrap_err().contains("grid_edges error: Generator would create 24 edges"));
+    }
+
+    #[test]
+    fn test_rule_growth_exceeding_edge_limit() {
+        let mut engine = GGLEngine::new().with_max_edges(20);
+
+        // Connecting every node to a hub adds one edge per node
+        let ggl_code = r#"
+            graph growth {
+                generate path { nodes: 15; }
+                rule attach {
+                    lhs { node a; }
+                    rhs { node a; node hub; edge: a -- hub; }
+                }
+                apply attach 3 times;
+            }
+        "#;
+
+        let result = engine.generate_from_ggl(ggl_code);
+        assert!(result.is_err());
+        assert!(result.err().unwrap().contains("limit of 20 edges"));
+    }
+
+    #[test]
+    fn test_declared_nodes_exceeding_limit() {
+        let mut engine = GGLEngine::new().with_max_nodes(5);
+
+        let ggl_code = r#"
+            graph declared {
+                for i in 0..10 {
+                    node "n{i}";
+                }
+            }
+        "#;
+
+        assert!(engine.generate_from_ggl(ggl_code).is_err());
+    }
 }
@@ -0,0 +1,120 @@
+use graph_generation_language::generators::{generate_path, generate_star};
+use graph_generation_language::layout::*;
+use graph_generation_language::types::Graph;
+use serde_json::Value;
+use std::collections::HashMap;
+
+fn path_graph(nodes: usize) -> Graph {
+    let mut params = HashMap::new();
+    params.insert("nodes".to_string(), Value::from(nodes));
+    generate_path(&params).unwrap()
+}
+
+fn position(graph: &Graph, id: &str) -> (f64, f64) {
+    let node = graph.get_node(id).unwrap();
+    (
+        node.metadata["x"].as_f64().unwrap(),
+        node.metadata["y"].as_f64().unwrap(),
+    )
+}
+
+#[test]
+fn test_circle_layout_radius() {
+    let mut graph = path_graph(8);
+    let options = LayoutOptions::default();
+    apply_layout(&mut graph, "circle", &options).unwrap();
+
+    let center = options.scale / 2.0;
+    for id in graph.nodes.keys() {
+        let (x, y) = position(&graph, id);
+        let radius = ((x - center).powi(2) + (y - center).powi(2)).sqrt();
+        assert!((radius - options.scale / 2.0).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn test_grid_layout_within_bounds() {
+    let mut graph = path_graph(10);
+    let options = LayoutOptions::default();
+    apply_layout(&mut graph, "grid", &options).unwrap();
+
+    for id in graph.nodes.keys() {
+        let (x, y) = position(&graph, id);
+        assert!((0.0..=options.scale).contains(&x));
+        assert!((0.0..=options.scale).contains(&y));
+    }
+    assert_eq!(position(&graph, "n0"), (0.0, 0.0));
+}
+
+#[test]
+fn test_random_layout_seeded() {
+    let options = LayoutOptions {
+        seed: Some(7),
+        ..LayoutOptions::default()
+    };
+    let mut first = path_graph(5);
+    let mut second = path_graph(5);
+    apply_layout(&mut first, "random", &options).unwrap();
+    apply_layout(&mut second, "random", &options).unwrap();
+    for id in first.nodes.keys() {
+        assert_eq!(position(&first, id), position(&second, id));
+    }
+}
+
+#[test]
+fn test_unknown_layout() {
+    let mut graph = path_graph(3);
+    assert!(apply_layout(&mut graph, "spiral", &LayoutOptions::default()).is_err());
+}
+
+#[test]
+fn test_force_layout_settles_at_ideal_edge_length() {
+    let mut graph = path_graph(2);
+    let options = LayoutOptions {
+        iterations: 200,
+        ..LayoutOptions::default()
+    };
+    apply_layout(&mut graph, "force", &options).unwrap();
+
+    let ideal = (options.scale * options.scale / 2.0).sqrt();
+    let (x0, y0) = position(&graph, "n0");
+    let (x1, y1) = position(&graph, "n1");
+    let distance = ((x0 - x1).powi(2) + (y0 - y1).powi(2)).sqrt();
+    assert!((distance - ideal).abs() < ideal * 0.05, "distance {distance}, ideal {ideal}");
+}
+
+#[test]
+fn test_force_layout_deterministic() {
+    let mut first = path_graph(6);
+    let mut second = path_graph(6);
+    apply_layout(&mut first, "force", &LayoutOptions::default()).unwrap();
+    apply_layout(&mut second, "force", &LayoutOptions::default()).unwrap();
+    for id in first.nodes.keys() {
+        assert_eq!(position(&first, id), position(&second, id));
+    }
+}
+
+#[test]
+fn test_concentric_layout_rings_by_degree() {
+    let mut params = HashMap::new();
+    params.insert("nodes".to_string(), Value::from(5));
+    let mut graph = generate_star(&params).unwrap();
+    let options = LayoutOptions::default();
+    apply_layout(&mut graph, "concentric", &options).unwrap();
+
+    let center = options.scale / 2.0;
+    let distance = |id: &str| {
+        let (x, y) = position(&graph, id);
+        ((x - center).powi(2) + (y - center).powi(2)).sqrt()
+    };
+    let hub = graph
+        .nodes
+        .keys()
+        .find(|id| graph.edges.values().filter(|e| &e.source == *id || &e.target == *id).count() > 1)
+        .unwrap()
+        .clone();
+    assert!(distance(&hub) < 1e-9);
+    for id in graph.nodes.keys().filter(|id| **id != hub) {
+        assert!((distance(id) - options.scale / 2.0).abs() < 1e-9);
+    }
+}
@@ -1,4 +1,4 @@
-use graph_generation_language::parser::{parse_ggl, Expression, Statement};
+use graph_generation_language::parser::{parse_ggl, BinaryOp, Expression, Statement};
 
 #[cfg(test)]
 mod lexical_tests {
@@ -222,6 +222,34 @@ mod lexical_tests {
         let ast = result.unwrap();
         assert_eq!(ast.statements.len(), 3);
     }
+
+    #[test]
+    fn test_doc_comments() {
+        let input = r#"
+            graph test {
+                # Entry point of the service.
+                #   Handles [all] requests.
+                node gateway;
+                node plain;
+                # Primary link
+                edge: gateway -> plain;
+            }
+        "#;
+
+        let ast = parse_ggl(input).unwrap();
+        match (&ast.statements[0], &ast.statements[1], &ast.statements[2]) {
+            (Statement::Node(documented), Statement::Node(plain), Statement::Edge(edge)) => {
+                assert_eq!(
+                    documented.doc.as_deref(),
+                    Some("Entry point of the service.\n  Handles [all] requests.")
+                );
+                assert_eq!(plain.doc, None);
+                assert_eq!(edge.doc.as_deref(), Some("Primary link"));
+                assert_eq!(edge.source, Expression::Identifier("gateway".to_string()));
+            }
+            _ => panic!("Expected two NodeDecls and an EdgeDecl"),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -400,6 +428,108 @@ mod node_declaration_tests {
             _ => panic!("Expected NodeDecl"),
         }
     }
+
+    #[test]
+    fn test_structured_attributes() {
+        let input = r#"
+            graph test {
+                node item [tags=["a", "b"], pos={x: 1, "y": 2.5}, empty=[]];
+            }
+        "#;
+
+        let result = parse_ggl(input);
+        assert!(result.is_ok(), "Failed to parse: {:?}", result.err());
+
+        let ast = result.unwrap();
+        match &ast.statements[0] {
+            Statement::Node(node) => {
+                assert_eq!(
+                    node.attributes[0],
+                    (
+                        "tags".to_string(),
+                        Expression::Array(vec![
+                            Expression::StringLiteral("a".to_string()),
+                            Expression::StringLiteral("b".to_string()),
+                        ])
+                    )
+                );
+                assert_eq!(
+                    node.attributes[1],
+                    (
+                        "pos".to_string(),
+                        Expression::Object(vec![
+                            ("x".to_string(), Expression::Integer(1)),
+                            ("y".to_string(), Expression::Float(2.5)),
+                        ])
+                    )
+                );
+                assert_eq!(node.attributes[2].1, Expression::Array(vec![]));
+            }
+            _ => panic!("Expected NodeDecl"),
+        }
+    }
+
+    #[test]
+    fn test_arithmetic_precedence() {
+        let input = r#"
+            graph test {
+                node item [size=base + scale * (2 - 1)];
+                edge: item--other;
+            }
+        "#;
+
+        let ast = parse_ggl(input).unwrap();
+        let ident = |name: &str| Box::new(Expression::Identifier(name.to_string()));
+        match &ast.statements[0] {
+            Statement::Node(node) => {
+                assert_eq!(
+                    node.attributes[0].1,
+                    Expression::Binary {
+                        op: BinaryOp::Add,
+                        left: ident("base"),
+                        right: Box::new(Expression::Binary {
+                            op: BinaryOp::Mul,
+                            left: ident("scale"),
+                            right: Box::new(Expression::Binary {
+                                op: BinaryOp::Sub,
+                                left: Box::new(Expression::Integer(2)),
+                                right: Box::new(Expression::Integer(1)),
+                            }),
+                        }),
+                    }
+                );
+            }
+            _ => panic!("Expected NodeDecl"),
+        }
+        // `--` without spaces is still an edge operator
+        match &ast.statements[1] {
+            Statement::Edge(edge) => assert!(!edge.directed),
+            _ => panic!("Expected EdgeDecl"),
+        }
+    }
+
+    #[test]
+    fn test_object_shorthand() {
+        let input = r#"
+            graph test {
+                node item [info={id, size: 2}];
+            }
+        "#;
+
+        let ast = parse_ggl(input).unwrap();
+        match &ast.statements[0] {
+            Statement::Node(node) => {
+                assert_eq!(
+                    node.attributes[0].1,
+                    Expression::Object(vec![
+                        ("id".to_string(), Expression::Identifier("id".to_string())),
+                        ("size".to_string(), Expression::Integer(2)),
+                    ])
+                );
+            }
+            _ => panic!("Expected NodeDecl"),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,292 @@
+use graph_generation_language::parser::{parse_ggl, Statement};
+use graph_generation_language::rules::Rule;
+use graph_generation_language::types::{Edge, Graph, Node};
+use std::collections::HashMap;
+
+/// Parses the first rule defined in a GGL program.
+fn parse_rule(ggl_code: &str) -> Rule {
+    let program = parse_ggl(ggl_code).unwrap();
+    program
+        .statements
+        .into_iter()
+        .find_map(|statement| match statement {
+            Statement::RuleDef(rule) => Some(Rule {
+                name: rule.name,
+                lhs: rule.lhs,
+                rhs: rule.rhs,
+            }),
+            _ => None,
+        })
+        .expect("program defines a rule")
+}
+
+fn path_graph(ids: &[&str]) -> Graph {
+    let mut graph = Graph::new();
+    for id in ids {
+        graph.add_node(id.to_string(), Node::new());
+    }
+    for (i, pair) in ids.windows(2).enumerate() {
+        graph.add_edge(
+            format!("e{i}"),
+            Edge::new(pair[0].to_string(), pair[1].to_string(), true),
+        );
+    }
+    graph
+}
+
+fn bindings(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs
+        .iter()
+        .map(|(pattern, node)| (pattern.to_string(), node.to_string()))
+        .collect()
+}
+
+#[test]
+fn test_apply_with_observer_reports_bindings() {
+    let rule = parse_rule(
+        r#"
+        graph g {
+            rule extend {
+                lhs { node A; node B; edge A -> B; }
+                rhs { node A; node B [visited=true]; edge A -> B; }
+            }
+        }
+    "#,
+    );
+    let mut graph = path_graph(&["a", "b", "c", "d"]);
+
+    let mut observed = Vec::new();
+    rule.apply_with_observer(&mut graph, 1, |m, graph| {
+        // Observers see the graph before the match is rewritten
+        let target = &graph.nodes[&m.node_mapping["B"]];
+        assert!(!target.metadata.contains_key("visited"));
+        observed.push(m.node_mapping.clone());
+    })
+    .unwrap();
+
+    assert_eq!(
+        observed,
+        vec![
+            bindings(&[("A", "a"), ("B", "b")]),
+            bindings(&[("A", "c"), ("B", "d")]),
+        ]
+    );
+    assert!(graph.nodes["d"].metadata.contains_key("visited"));
+}
+
+#[test]
+fn test_find_matches_is_a_dry_run() {
+    let rule = parse_rule(
+        r#"
+        graph g {
+            rule close_triangle {
+                lhs { node A; node B; node C; edge A -> B; edge B -> C; }
+                rhs { node A; node B; node C; edge A -> B; edge B -> C; edge C -> A; }
+            }
+        }
+    "#,
+    );
+    let graph = path_graph(&["a", "b", "c", "d", "e", "f", "g"]);
+
+    let matches = rule.find_matches(&graph).unwrap();
+    assert_eq!(matches.len(), 2);
+    assert_eq!(
+        matches[0].node_mapping,
+        bindings(&[("A", "a"), ("B", "b"), ("C", "c")])
+    );
+    assert_eq!(graph.edge_count(), 6);
+}
+
+#[test]
+fn test_wildcard_attribute_matches_presence() {
+    let rule = parse_rule(
+        r#"
+        graph g {
+            rule track {
+                lhs { node N [status=*]; }
+                rhs { node N [tracked=true]; }
+            }
+        }
+    "#,
+    );
+    let mut graph = Graph::new();
+    graph.add_node(
+        "up".to_string(),
+        Node::new().with_metadata("status".to_string(), "up".into()),
+    );
+    graph.add_node(
+        "down".to_string(),
+        Node::new().with_metadata("status".to_string(), false.into()),
+    );
+    graph.add_node(
+        "plain".to_string(),
+        Node::new().with_metadata("other".to_string(), 1.into()),
+    );
+
+    let mut matched: Vec<String> = rule
+        .find_matches(&graph)
+        .unwrap()
+        .into_iter()
+        .map(|m| m.node_mapping["N"].clone())
+        .collect();
+    matched.sort();
+    assert_eq!(matched, vec!["down", "up"]);
+
+    rule.apply(&mut graph, 1).unwrap();
+    assert!(graph.nodes["up"].metadata.contains_key("tracked"));
+    assert!(graph.nodes["down"].metadata.contains_key("tracked"));
+    assert!(!graph.nodes["plain"].metadata.contains_key("tracked"));
+}
+
+#[test]
+fn test_wildcard_outside_lhs_is_rejected() {
+    let rule = parse_rule(
+        r#"
+        graph g {
+            rule broken {
+                lhs { node N; }
+                rhs { node N [status=*]; }
+            }
+        }
+    "#,
+    );
+    let mut graph = path_graph(&["a"]);
+    let err = rule.apply(&mut graph, 1).unwrap_err();
+    assert!(err.contains("only allowed in rule LHS"));
+
+    let mut engine = graph_generation_language::GGLEngine::new();
+    let err = engine
+        .generate_from_ggl("graph g { node a [status=*]; }")
+        .unwrap_err();
+    assert!(err.contains("only allowed in rule LHS"));
+}
+
+#[test]
+fn test_edge_attribute_matching() {
+    let rule = parse_rule(
+        r#"
+        graph g {
+            rule befriend {
+                lhs { node A; node B; edge A -- B [type="friend"]; }
+                rhs { node A [social=true]; node B [social=true]; edge A -- B; }
+            }
+        }
+    "#,
+    );
+    let mut graph = Graph::new();
+    for id in ["a", "b", "c", "d"] {
+        graph.add_node(id.to_string(), Node::new());
+    }
+    graph.add_edge(
+        "friends".to_string(),
+        Edge::new("a".to_string(), "b".to_string(), false)
+            .with_metadata("type".to_string(), "friend".into()),
+    );
+    graph.add_edge(
+        "colleagues".to_string(),
+        Edge::new("c".to_string(), "d".to_string(), false)
+            .with_metadata("type".to_string(), "colleague".into()),
+    );
+
+    rule.apply(&mut graph, 5).unwrap();
+
+    let social = |id: &str| graph.nodes[id].metadata.contains_key("social");
+    assert!(social("a") && social("b"));
+    assert!(!social("c") && !social("d"));
+
+    let any_type = parse_rule(
+        r#"
+        graph g {
+            rule typed {
+                lhs { node A; node B; edge A -- B [type=*]; }
+                rhs { node A; node B; edge A -- B; }
+            }
+        }
+    "#,
+    );
+    assert_eq!(any_type.find_matches(&graph).unwrap().len(), 2);
+}
+
+#[test]
+fn test_rhs_arithmetic_on_attribute_variables() {
+    let rule = parse_rule(
+        r#"
+        graph g {
+            rule tick {
+                lhs { node N [count=$C, step=$S]; }
+                rhs { node N [count=$C + $S, label="tick"]; }
+            }
+        }
+    "#,
+    );
+    let mut graph = Graph::new();
+    graph.add_node(
+        "counter".to_string(),
+        Node::new()
+            .with_metadata("count".to_string(), 0.into())
+            .with_metadata("step".to_string(), 1.into()),
+    );
+    graph.add_node("no_counter".to_string(), Node::new());
+
+    rule.apply(&mut graph, 7).unwrap();
+
+    assert_eq!(graph.nodes["counter"].metadata["count"], 7);
+    assert_eq!(graph.nodes["counter"].metadata["label"], "tick");
+    assert!(graph.nodes["no_counter"].metadata.is_empty());
+}
+
+#[test]
+fn test_bare_identifiers_are_compared_as_strings() {
+    let rule = parse_rule(
+        r#"
+        graph g {
+            rule mark {
+                lhs { node N [status=active]; }
+                rhs { node N [status=active, hit=true]; }
+            }
+        }
+    "#,
+    );
+    let mut graph = Graph::new();
+    graph.add_node(
+        "busy".to_string(),
+        Node::new().with_metadata("status".to_string(), "active".into()),
+    );
+    graph.add_node(
+        "idle".to_string(),
+        Node::new().with_metadata("status".to_string(), "idle".into()),
+    );
+
+    rule.apply(&mut graph, 5).unwrap();
+
+    assert_eq!(graph.nodes["busy"].metadata["hit"], true);
+    assert_eq!(graph.nodes["idle"].metadata["status"], "idle");
+    assert!(!graph.nodes["idle"].metadata.contains_key("hit"));
+}
+
+#[test]
+fn test_unbound_attribute_variables_are_rejected() {
+    let rule = parse_rule(
+        r#"
+        graph g {
+            rule broken {
+                lhs { node N [count=$C]; }
+                rhs { node N [count=$D + 1]; }
+            }
+        }
+    "#,
+    );
+    let mut graph = Graph::new();
+    graph.add_node(
+        "n".to_string(),
+        Node::new().with_metadata("count".to_string(), 0.into()),
+    );
+    let err = rule.apply(&mut graph, 1).unwrap_err();
+    assert!(err.contains("Attribute variable '$D' is not bound by the rule LHS"));
+
+    let mut engine = graph_generation_language::GGLEngine::new();
+    let err = engine
+        .generate_from_ggl("graph g { node a [count=$C]; }")
+        .unwrap_err();
+    assert!(err.contains("only allowed in rule attributes"));
+}
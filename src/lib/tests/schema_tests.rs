@@ -0,0 +1,63 @@
+use graph_generation_language::schema::*;
+use graph_generation_language::GGLEngine;
+
+fn schema() -> Schema {
+    Schema::new()
+        .require_node("name", ValueType::String)
+        .require_node("age", ValueType::Integer)
+        .require_edge("weight", ValueType::Number)
+}
+
+#[test]
+fn test_conforming_graph() {
+    let mut engine = GGLEngine::new();
+    engine
+        .generate_from_ggl(
+            r#"
+            graph people {
+                node alice [name="Alice", age=30];
+                node bob [name="Bob", age=25];
+                edge: alice -- bob [weight=0.5];
+            }
+        "#,
+        )
+        .unwrap();
+
+    assert!(engine.validate_against_schema(&schema()).is_ok());
+}
+
+#[test]
+fn test_non_conforming_graph() {
+    let mut engine = GGLEngine::new();
+    engine
+        .generate_from_ggl(
+            r#"
+            graph people {
+                node alice [name="Alice", age=30.5];
+                node bob [age=25];
+                edge friends: alice -- bob [weight="heavy"];
+            }
+        "#,
+        )
+        .unwrap();
+
+    let violations = engine.validate_against_schema(&schema()).unwrap_err();
+    let summary: Vec<(&str, &str, &str)> = violations
+        .iter()
+        .map(|v| (v.element, v.id.as_str(), v.key.as_str()))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("node", "alice", "age"),
+            ("node", "bob", "name"),
+            ("edge", "friends", "weight"),
+        ]
+    );
+    assert_eq!(violations[1].found, None);
+    assert_eq!(violations[1].to_string(), "node 'bob': missing 'name' (string)");
+    assert_eq!(
+        violations[2].to_string(),
+        "edge 'friends': 'weight' should be a number, found \"heavy\""
+    );
+}
@@ -3,6 +3,8 @@
 //! This crate provides WebAssembly bindings for the GGL library, allowing
 //! GGL to be used in web browsers and other JavaScript environments.
 
+use graph_generation_language::layout::{self, LayoutOptions};
+use graph_generation_language::types::Graph;
 use graph_generation_language::GGLEngine;
 use wasm_bindgen::prelude::*;
 
@@ -150,6 +152,30 @@ impl WASMGGLEngine {
         serde_json::to_string_pretty(self.inner.get_graph())
             .map_err(|e| JsValue::from_str(&format!("Serialization error: {e}")))
     }
+
+    /// Computes a layout for a graph, returning it as JSON with `x`/`y` node metadata.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph_json` - A graph in the JSON format produced by `generate_from_ggl`
+    /// * `algorithm` - The layout to use: `circle`, `grid`, `concentric`, `random` or `force`
+    ///
+    /// # Examples
+    ///
+    /// ```javascript
+    /// const engine = new GGLEngine();
+    /// const graphJson = engine.generate_from_ggl(gglCode);
+    /// const laidOut = JSON.parse(engine.compute_layout(graphJson, "force"));
+    /// ```
+    #[wasm_bindgen]
+    pub fn compute_layout(&self, graph_json: &str, algorithm: &str) -> Result<String, JsValue> {
+        let mut graph: Graph = serde_json::from_str(graph_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid graph JSON: {e}")))?;
+        layout::apply_layout(&mut graph, algorithm, &LayoutOptions::default())
+            .map_err(|e| JsValue::from_str(&e))?;
+        serde_json::to_string(&graph)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {e}")))
+    }
 }
 
 /// Utility function to parse GGL code and return the result as JSON.
@@ -0,0 +1,30 @@
+//! Tests for the WebAssembly bindings.
+
+#![cfg(target_arch = "wasm32")]
+
+use ggl_wasm::WASMGGLEngine;
+use serde_json::Value;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen_test]
+fn test_compute_circle_layout() {
+    let mut engine = WASMGGLEngine::new();
+    let graph_json = engine
+        .generate_from_ggl(
+            r#"
+            graph ring {
+                generate cycle {
+                    nodes: 5;
+                }
+            }
+        "#,
+        )
+        .unwrap();
+
+    let result = engine.compute_layout(&graph_json, "circle").unwrap();
+    let graph: Value = serde_json::from_str(&result).unwrap();
+    for node in graph["nodes"].as_object().unwrap().values() {
+        assert!(node["metadata"]["x"].is_number());
+        assert!(node["metadata"]["y"].is_number());
+    }
+}